@@ -9,13 +9,14 @@ extern crate ethabi;
 
 mod error;
 
-use std::fs::File;
+use std::fs::{self, File};
 use std::env;
+use std::path::Path;
 use docopt::Docopt;
 use hex::{ToHex, FromHex};
 use ethabi::param_type::{ParamType, Reader};
 use ethabi::token::{Token, Tokenizer, StrictTokenizer, LenientTokenizer};
-use ethabi::{encode, decode, Contract, Function, Event, Hash};
+use ethabi::{encode, decode, find_collisions, Artifact, Contract, Function, Event, Hash};
 use error::{Error, ResultExt};
 
 pub const ETHABI: &str = r#"
@@ -28,6 +29,8 @@ Usage:
     ethabi decode function <abi-path> <function-name> <data>
     ethabi decode params [-t <type>]... <data>
     ethabi decode log <abi-path> <event-name> [-l <topic>]... <data>
+    ethabi diff <abi-path> <new-abi-path>
+    ethabi collisions <abi-dir>
     ethabi -h | --help
 
 Options:
@@ -37,9 +40,19 @@ Options:
 Commands:
     encode             Encode ABI call.
     decode             Decode ABI call result.
-    function           Load function from json ABI file.
+    diff               Compare two versions of a contract interface,
+                       listing breaking changes first.
+    collisions         Find function selectors and event topics shared by
+                       contracts in json ABI files or compiler artifacts
+                       in the directory.
+    function           Load function from json ABI file or compiler artifact.
+                       Overloaded functions are selected by signature,
+                       eg. `transfer(address,uint256)`. Contracts of
+                       artifacts with more contracts are selected by name,
+                       eg. `combined.json:Token`.
     params             Specify types of input params inline.
-    log                Decode event log.
+    log                Decode event log. Overloaded events are selected
+                       by signature, eg. `Transfer(address,address,uint256)`.
 "#;
 
 #[derive(Debug, Deserialize)]
@@ -49,7 +62,11 @@ struct Args {
 	cmd_function: bool,
 	cmd_params: bool,
 	cmd_log: bool,
+	cmd_diff: bool,
+	cmd_collisions: bool,
 	arg_abi_path: String,
+	arg_new_abi_path: String,
+	arg_abi_dir: String,
 	arg_function_name: String,
 	arg_event_name: String,
 	arg_param: Vec<String>,
@@ -59,6 +76,27 @@ struct Args {
 	flag_lenient: bool,
 }
 
+impl Args {
+	/// Restores negative numbers escaped by `escape_negative` in all positional values.
+	fn unescape_negative(&mut self) {
+		for arg in vec![
+			&mut self.arg_abi_path,
+			&mut self.arg_new_abi_path,
+			&mut self.arg_abi_dir,
+			&mut self.arg_function_name,
+			&mut self.arg_event_name,
+			&mut self.arg_data,
+		] {
+			*arg = unescape_negative(arg);
+		}
+		for args in vec![&mut self.arg_param, &mut self.arg_type, &mut self.arg_topic] {
+			for arg in args.iter_mut() {
+				*arg = unescape_negative(arg);
+			}
+		}
+	}
+}
+
 fn main() {
 	let result = execute(env::args());
 
@@ -77,8 +115,7 @@ fn print_err(err: &Error) {
 }
 
 fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item=S>, S: AsRef<str> {
-	let args: Args = Docopt::new(ETHABI)
-		.and_then(|d| d.argv(command).deserialize())?;
+	let args = parse_args(command)?;
 
 	if args.cmd_encode && args.cmd_function {
 		encode_input(&args.arg_abi_path, &args.arg_function_name, &args.arg_param, args.flag_lenient)
@@ -90,22 +127,69 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 		decode_params(&args.arg_type, &args.arg_data)
 	} else if args.cmd_decode && args.cmd_log {
 		decode_log(&args.arg_abi_path, &args.arg_event_name, &args.arg_topic, &args.arg_data)
+	} else if args.cmd_diff {
+		diff(&args.arg_abi_path, &args.arg_new_abi_path)
+	} else if args.cmd_collisions {
+		collisions(&args.arg_abi_dir)
 	} else {
 		unreachable!()
 	}
 }
 
+fn parse_args<S, I>(command: I) -> Result<Args, Error> where I: IntoIterator<Item=S>, S: AsRef<str> {
+	let command = command.into_iter().map(|arg| escape_negative(arg.as_ref()));
+	let mut args: Args = Docopt::new(ETHABI)
+		.and_then(|d| d.argv(command).deserialize())?;
+	args.unescape_negative();
+	Ok(args)
+}
+
+/// Docopt takes any argument starting with `-` for a flag, so negative numbers
+/// are escaped before parsing the command line.
+fn escape_negative(arg: &str) -> String {
+	if arg.starts_with('-') && arg[1..].starts_with(|c: char| c.is_ascii_digit()) {
+		format!("\\{}", arg)
+	} else {
+		arg.to_owned()
+	}
+}
+
+fn unescape_negative(arg: &str) -> String {
+	if arg.starts_with("\\-") {
+		arg[1..].to_owned()
+	} else {
+		arg.to_owned()
+	}
+}
+
+/// Loads contract from `path`, or contract `Name` of an artifact with more contracts from `path:Name`.
+fn load_contract(path: &str) -> Result<Contract, Error> {
+	let artifact = match path.rfind(':') {
+		Some(index) if !Path::new(path).exists() => {
+			Artifact::load_by_name(File::open(&path[..index])?, &path[index + 1..])?
+		},
+		_ => Artifact::load(File::open(path)?)?,
+	};
+	Ok(artifact.contract)
+}
+
 fn load_function(path: &str, function: &str) -> Result<Function, Error> {
-	let file = File::open(path)?;
-	let contract = Contract::load(file)?;
-	let function = contract.function(function)?.clone();
+	let contract = load_contract(path)?;
+	let function = if function.contains('(') {
+		contract.function_by_signature(function)?
+	} else {
+		contract.function(function)?
+	}.clone();
 	Ok(function)
 }
 
 fn load_event(path: &str, event: &str) -> Result<Event, Error> {
-	let file = File::open(path)?;
-	let contract = Contract::load(file)?;
-	let event = contract.event(event)?.clone();
+	let contract = load_contract(path)?;
+	let event = if event.contains('(') {
+		contract.event_by_signature(event)?
+	} else {
+		contract.event(event)?
+	}.clone();
 	Ok(event)
 }
 
@@ -160,7 +244,7 @@ fn decode_call_output(path: &str, function: &str, data: &str) -> Result<String,
 
 	let result = types.iter()
 		.zip(tokens.iter())
-		.map(|(ty, to)| format!("{} {}", ty.kind, to))
+		.map(|(ty, to)| format!("{} {}", ty.kind, to.format_typed(&ty.kind)))
 		.collect::<Vec<String>>()
 		.join("\n");
 
@@ -180,7 +264,7 @@ fn decode_params(types: &[String], data: &str) -> Result<String, Error> {
 
 	let result = types.iter()
 		.zip(tokens.iter())
-		.map(|(ty, to)| format!("{} {}", ty, to))
+		.map(|(ty, to)| format!("{} {}", ty, to.format_typed(ty)))
 		.collect::<Vec<String>>()
 		.join("\n");
 
@@ -203,9 +287,41 @@ fn decode_log(path: &str, event: &str, topics: &[String], data: &str) -> Result<
 	Ok(result)
 }
 
+fn diff(path: &str, new_path: &str) -> Result<String, Error> {
+	let diff = load_contract(path)?.diff(&load_contract(new_path)?);
+
+	let breaking = diff.breaking().into_iter().map(|change| format!("breaking {}", change));
+	let compatible = diff.compatible().into_iter().map(|change| format!("compatible {}", change));
+	let result = breaking.chain(compatible).collect::<Vec<String>>();
+
+	Ok(result.join("\n"))
+}
+
+fn collisions(dir: &str) -> Result<String, Error> {
+	let mut paths = fs::read_dir(dir)?
+		.map(|entry| entry.map(|e| e.path()))
+		.collect::<Result<Vec<_>, _>>()?;
+	paths.sort();
+
+	let mut contracts = vec![];
+	for path in paths.into_iter().filter(|path| path.is_file()) {
+		let file_name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+		let artifacts = Artifact::load_all(File::open(&path)?)
+			.chain_err(|| format!("Cannot load contract abi from `{}`", path.display()))?;
+		for artifact in artifacts {
+			contracts.push((artifact.name.unwrap_or_else(|| file_name.clone()), artifact.contract));
+		}
+	}
+
+	let collisions = find_collisions(contracts.iter().map(|&(ref name, ref contract)| (name.as_str(), contract)));
+	let result = collisions.iter().map(ToString::to_string).collect::<Vec<String>>();
+
+	Ok(result.join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
-	use super::execute;
+	use super::{execute, parse_args};
 
 	#[test]
 	fn simple_encode() {
@@ -214,15 +330,23 @@ mod tests {
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
-	// TODO: parsing negative values is not working
 	#[test]
-	#[ignore]
 	fn int_encode() {
-		let command = "ethabi encode paramas -v int256 -2 --lenient".split(" ");
+		let command = "ethabi encode params -v int256 -2 --lenient".split(" ");
 		let expected = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn negative_values_are_not_flags() {
+		let command = "ethabi encode params -v int8 -128 -v fixed16x1 -1.5 --lenient".split(" ");
+		let expected = "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff1";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let args = parse_args("ethabi decode params -t int256 -2".split(" ")).unwrap();
+		assert_eq!(args.arg_data, "-2");
+	}
+
 	#[test]
 	fn multi_encode() {
 		let command = "ethabi encode params -v bool 1 -v string gavofyork -v bool 0".split(" ");
@@ -244,6 +368,23 @@ mod tests {
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
+	#[test]
+	fn abi_encode_overloaded() {
+		let command = "ethabi encode function ../res/overloaded.abi transfer(address,uint256) -p 1111111111111111111111111111111111111111 -p 1 --lenient".split(" ");
+		let expected = "a9059cbb00000000000000000000000011111111111111111111111111111111111111110000000000000000000000000000000000000000000000000000000000000001";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi encode function ../res/overloaded.abi transfer -p 1111111111111111111111111111111111111111 -p 1 --lenient".split(" ");
+		assert!(execute(command).is_err());
+	}
+
+	#[test]
+	fn artifact_encode() {
+		let command = "ethabi encode function ../res/test.artifact.json foo -p 1".split(" ");
+		let expected = "455575780000000000000000000000000000000000000000000000000000000000000001";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
 	#[test]
 	fn simple_decode() {
 		let command = "ethabi decode params -t bool 0000000000000000000000000000000000000000000000000000000000000001".split(" ");
@@ -254,7 +395,14 @@ mod tests {
 	#[test]
 	fn int_decode() {
 		let command = "ethabi decode params -t int256 fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe".split(" ");
-		let expected = "int256 fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe";
+		let expected = "int256 -2";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn fixed_decode() {
+		let command = "ethabi decode params -t fixed16x1 -t ufixed16x2 ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff1d0000000000000000000000000000000000000000000000000000000000000096".split(" ");
+		let expected = "fixed16x1 -22.7\nufixed16x2 1.5";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
 
@@ -290,4 +438,45 @@ bool false";
 b 4444444444444444444444444444444444444444";
 		assert_eq!(execute(command).unwrap(), expected);
 	}
+
+	#[test]
+	fn log_decode_by_signature() {
+		let command = "ethabi decode log ../res/event.abi Event(bool,address) -l 0000000000000000000000000000000000000000000000000000000000000001 0000000000000000000000004444444444444444444444444444444444444444".split(" ");
+		let expected =
+"a true
+b 4444444444444444444444444444444444444444";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
+
+	#[test]
+	fn abi_diff() {
+		let command = "ethabi diff ../res/test.abi ../res/foo.abi".split(" ");
+		let expected =
+"breaking removed function foo(bool)
+compatible added function bar(address)";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi diff ../res/test.abi ../res/test.artifact.json".split(" ");
+		assert_eq!(execute(command).unwrap(), "");
+	}
+
+	#[test]
+	fn contract_selected_by_name() {
+		let command = "ethabi encode function ../res/combined.json:Token transfer -p 1111111111111111111111111111111111111111 -p 42 --lenient".split(" ");
+		let expected = "a9059cbb0000000000000000000000001111111111111111111111111111111111111111000000000000000000000000000000000000000000000000000000000000002a";
+		assert_eq!(execute(command).unwrap(), expected);
+
+		let command = "ethabi diff ../res/combined.json:IToken ../res/combined.json:Token".split(" ");
+		assert_eq!(execute(command).unwrap(), "compatible added function transfer(address,uint256)");
+
+		assert!(execute("ethabi encode function ../res/combined.json transfer -p 1111111111111111111111111111111111111111 -p 42 --lenient".split(" ")).is_err());
+		assert!(execute("ethabi encode function ../res/combined.json:Missing transfer".split(" ")).is_err());
+	}
+
+	#[test]
+	fn abi_collisions() {
+		let command = "ethabi collisions ../res/diamond".split(" ");
+		let expected = "selector 0x42966c68: OwnershipFacet.burn(uint256), StorageFacet.collate_propagate_storage(bytes16)";
+		assert_eq!(execute(command).unwrap(), expected);
+	}
 }
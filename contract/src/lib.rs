@@ -11,5 +11,17 @@ macro_rules! use_contract {
 			#[ethabi_contract_options(path = $path)]
 			struct _Dummy;
 		}
-	}
+	};
+	($module: ident, $path: expr, $name: expr) => {
+		#[allow(dead_code)]
+		#[allow(missing_docs)]
+		#[allow(unused_imports)]
+		#[allow(unused_mut)]
+		#[allow(unused_variables)]
+		pub mod $module {
+			#[derive(EthabiContract)]
+			#[ethabi_contract_options(path = $path, name = $name)]
+			struct _Dummy;
+		}
+	};
 }
@@ -3,7 +3,7 @@ use proc_macro2::TokenStream;
 
 use super::{
 	input_names, template_param_type, rust_type, get_template_names, to_token, from_template_param,
-	to_ethabi_param_vec,
+	to_ethabi_param_vec, to_state_mutability, to_rustdoc,
 };
 
 /// Structure used to generate contract's constructor interface.
@@ -12,10 +12,19 @@ pub struct Constructor {
 	inputs_definitions: Vec<TokenStream>,
 	tokenize: Vec<TokenStream>,
 	recreate_inputs: TokenStream,
+	state_mutability: TokenStream,
+	docs: Vec<TokenStream>,
 }
 
 impl<'a> From<&'a ethabi::Constructor> for Constructor {
 	fn from(c: &'a ethabi::Constructor) -> Self {
+		Constructor::new(c, None)
+	}
+}
+
+impl Constructor {
+	/// Creates constructor interface generator, documented with given NatSpec documentation.
+	pub fn new(c: &ethabi::Constructor, docs: Option<&ethabi::Documentation>) -> Self {
 		// [param0, hello_world, param2]
 		let input_names = input_names(&c.inputs);
 
@@ -46,28 +55,39 @@ impl<'a> From<&'a ethabi::Constructor> for Constructor {
 			.map(|(param_name, param)| to_token(&from_template_param(&param.kind, &param_name), &param.kind))
 			.collect();
 
+		let names: Vec<_> = c.inputs.iter().map(|param| param.name.as_str()).collect();
+		let mut docs = to_rustdoc(docs, &names, &[]);
+		if !docs.is_empty() {
+			// separates NatSpec from the generated description
+			docs.push(quote! { #[doc = ""] });
+		}
+
 		Constructor {
 			inputs_declarations,
 			inputs_definitions,
 			tokenize,
 			recreate_inputs: to_ethabi_param_vec(&c.inputs),
+			state_mutability: to_state_mutability(c.state_mutability),
+			docs,
 		}
 	}
-}
 
-impl Constructor {
 	/// Generates contract constructor interface.
 	pub fn generate(&self) -> TokenStream {
 		let declarations = &self.inputs_declarations;
 		let definitions = &self.inputs_definitions;
 		let tokenize = &self.tokenize;
 		let recreate_inputs = &self.recreate_inputs;
+		let state_mutability = &self.state_mutability;
+		let docs = &self.docs;
 
 		quote! {
+			#(#docs)*
 			/// Encodes a call to contract's constructor.
 			pub fn constructor<#(#declarations),*>(#(#definitions),*) -> ethabi::Bytes {
 				let c = ethabi::Constructor {
 					inputs: #recreate_inputs,
+					state_mutability: #state_mutability,
 				};
 				let tokens = vec![#(#tokenize),*];
 				c.encode_input(code, &tokens).expect(INTERNAL_ERR)
@@ -85,6 +105,7 @@ mod tests {
 	fn test_no_params() {
 		let ethabi_constructor = ethabi::Constructor {
 			inputs: vec![],
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let c = Constructor::from(&ethabi_constructor);
@@ -94,6 +115,7 @@ mod tests {
 			pub fn constructor<>(code: ethabi::Bytes) -> ethabi::Bytes {
 				let c = ethabi::Constructor {
 					inputs: vec![],
+					state_mutability: ethabi::StateMutability::NonPayable,
 				};
 				let tokens = vec![];
 				c.encode_input(code, &tokens).expect(INTERNAL_ERR)
@@ -110,9 +132,11 @@ mod tests {
 				ethabi::Param {
 					name: "foo".into(),
 					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
 					components: vec![],
 				}
 			],
+			state_mutability: ethabi::StateMutability::Payable,
 		};
 
 		let c = Constructor::from(&ethabi_constructor);
@@ -123,8 +147,11 @@ mod tests {
 				let c = ethabi::Constructor {
 					inputs: vec![ethabi::Param {
 						name: "foo".to_owned(),
-						kind: ethabi::ParamType::Uint(256usize)
+						kind: ethabi::ParamType::Uint(256usize),
+						internal_type: None,
+						components: vec![],
 					}],
+					state_mutability: ethabi::StateMutability::Payable,
 				};
 				let tokens = vec![ethabi::Token::Uint(foo.into())];
 				c.encode_input(code, &tokens).expect(INTERNAL_ERR)
@@ -1,4 +1,4 @@
-use ethabi;
+use ethabi::{self, Entry};
 use proc_macro2::TokenStream;
 use constructor::Constructor;
 use function::Function;
@@ -14,9 +14,13 @@ pub struct Contract {
 impl<'a> From<&'a ethabi::Contract> for Contract {
 	fn from(c: &'a ethabi::Contract) -> Self {
 		Contract {
-			constructor: c.constructor.as_ref().map(Into::into),
-			functions: c.functions().map(Into::into).collect(),
-			events: c.events().map(Into::into).collect(),
+			constructor: c.constructor().map(|constructor| Constructor::new(constructor, c.docs(&Entry::Constructor))),
+			functions: c.functions()
+				.map(|f| Function::new(f, c.docs(&Entry::Function(f.signature()))))
+				.collect(),
+			events: c.events()
+				.map(|e| Event::new(e, c.docs(&Entry::Event(e.signature_string()))))
+				.collect(),
 		}
 	}
 }
@@ -63,12 +67,7 @@ mod test {
 
 	#[test]
 	fn test_no_body() {
-		let ethabi_contract = ethabi::Contract {
-			constructor: None,
-			functions: Default::default(),
-			events: Default::default(),
-			fallback: false,
-		};
+		let ethabi_contract = ethabi::Contract::default();
 
 		let c = Contract::from(&ethabi_contract);
 
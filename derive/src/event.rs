@@ -3,7 +3,7 @@ use heck::{SnakeCase, CamelCase};
 use proc_macro2::TokenStream;
 use syn::export::Span;
 
-use super::{rust_type, to_syntax_string, from_token, get_template_names, to_token};
+use super::{rust_type, to_syntax_string, from_token, get_template_names, to_token, to_rustdoc};
 
 /// Structure used to generate contract's event interface.
 pub struct Event {
@@ -16,10 +16,27 @@ pub struct Event {
 	filter_definitions: Vec<TokenStream>,
 	filter_init: Vec<TokenStream>,
 	anonymous: bool,
+	docs: Vec<TokenStream>,
 }
 
 impl<'a> From<&'a ethabi::Event> for Event {
 	fn from(e: &'a ethabi::Event) -> Self {
+		Event::new(e, None)
+	}
+}
+
+/// Returns type of the decoded log param. Indexed strings, bytes, arrays and tuples are decoded as their topic hash.
+fn log_kind(param: &ethabi::EventParam) -> ethabi::ParamType {
+	match param.kind {
+		ethabi::ParamType::String | ethabi::ParamType::Bytes | ethabi::ParamType::Array(_) |
+		ethabi::ParamType::FixedArray(_, _) | ethabi::ParamType::Tuple(_) if param.indexed => ethabi::ParamType::FixedBytes(32),
+		ref kind => kind.clone(),
+	}
+}
+
+impl Event {
+	/// Creates event interface generator, documented with given NatSpec documentation.
+	pub fn new(e: &ethabi::Event, docs: Option<&ethabi::Documentation>) -> Self {
 		let names: Vec<_> = e.inputs
 			.iter()
 			.enumerate()
@@ -34,7 +51,7 @@ impl<'a> From<&'a ethabi::Event> for Event {
 			}).collect();
 		let kinds: Vec<_> = e.inputs
 			.iter()
-			.map(|param| rust_type(&param.kind))
+			.map(|param| rust_type(&log_kind(param)))
 			.collect();
 		let log_fields= names.iter().zip(kinds.iter())
 			.map(|(param_name, kind)| quote! { pub #param_name: #kind })
@@ -44,7 +61,7 @@ impl<'a> From<&'a ethabi::Event> for Event {
 
 		let to_log: Vec<_> = e.inputs
 			.iter()
-			.map(|param| from_token(&param.kind, &log_iter))
+			.map(|param| from_token(&log_kind(param), &log_iter))
 			.collect();
 
 		let log_init = names.iter().zip(to_log.iter())
@@ -102,12 +119,16 @@ impl<'a> From<&'a ethabi::Event> for Event {
 				ethabi::EventParam {
 					name: #name.to_owned(),
 					kind: #kind,
-					indexed: #indexed
+					indexed: #indexed,
+					internal_type: None,
+					components: vec![]
 				}
 			}
 		}).collect::<Vec<_>>();
 		let recreate_inputs_quote = quote! { vec![ #(#event_inputs),* ] };
 
+		let param_names: Vec<_> = e.inputs.iter().map(|param| param.name.as_str()).collect();
+
 		Event {
 			name: e.name.clone(),
 			log_fields,
@@ -118,17 +139,18 @@ impl<'a> From<&'a ethabi::Event> for Event {
 			filter_declarations,
 			filter_definitions,
 			filter_init,
+			docs: to_rustdoc(docs, &param_names, &[]),
 		}
 	}
-}
 
-impl Event {
 	/// Generates event log struct.
 	pub fn generate_log(&self) -> TokenStream {
 		let name = syn::Ident::new(&self.name.to_camel_case(), Span::call_site());
 		let log_fields = &self.log_fields;
+		let docs = &self.docs;
 
 		quote! {
+			#(#docs)*
 			#[derive(Debug, Clone, PartialEq)]
 			pub struct #name {
 				#(#log_fields),*
@@ -148,8 +170,10 @@ impl Event {
 		let filter_declarations = &self.filter_declarations;
 		let filter_definitions = &self.filter_definitions;
 		let wildcard_filter_params = &self.wildcard_filter_params;
+		let docs = &self.docs;
 
 		quote! {
+			#(#docs)*
 			pub mod #name {
 				use ethabi;
 				use super::INTERNAL_ERR;
@@ -212,6 +236,43 @@ mod tests {
 		assert_eq!(expected.to_string(), e.generate_log().to_string());
 	}
 
+	#[test]
+	fn test_documented_log() {
+		let ethabi_event = ethabi::Event {
+			name: "hello".into(),
+			inputs: vec![ethabi::EventParam {
+				name: "foo".into(),
+				kind: ethabi::ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}],
+			anonymous: false,
+		};
+		let docs = ethabi::Documentation {
+			notice: Some("Emitted on hello.".into()),
+			details: None,
+			params: vec![("foo".to_owned(), "Greeted address.".to_owned())].into_iter().collect(),
+			returns: Default::default(),
+		};
+
+		let e = Event::new(&ethabi_event, Some(&docs));
+
+		let expected = quote! {
+			#[doc = " Emitted on hello."]
+			#[doc = " "]
+			#[doc = " # Params"]
+			#[doc = " "]
+			#[doc = " - `foo`: Greeted address."]
+			#[derive(Debug, Clone, PartialEq)]
+			pub struct Hello {
+				pub foo: ethabi::Address
+			}
+		};
+
+		assert_eq!(expected.to_string(), e.generate_log().to_string());
+	}
+
 	#[test]
 	fn test_empty_event() {
 		let ethabi_event = ethabi::Event {
@@ -267,7 +328,9 @@ mod tests {
 			inputs: vec![ethabi::EventParam {
 				name: "foo".into(),
 				kind: ethabi::ParamType::Address,
-				indexed: true
+				indexed: true,
+				internal_type: None,
+				components: vec![]
 			}],
 			anonymous: false,
 		};
@@ -285,7 +348,9 @@ mod tests {
 						inputs: vec![ethabi::EventParam {
 							name: "foo".to_owned(),
 							kind: ethabi::ParamType::Address,
-							indexed: true
+							indexed: true,
+							internal_type: None,
+							components: vec![]
 						}],
 						anonymous: false,
 					}
@@ -326,7 +391,9 @@ mod tests {
 			inputs: vec![ethabi::EventParam {
 				name: "foo".into(),
 				kind: ethabi::ParamType::Address,
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}],
 			anonymous: false,
 		};
@@ -350,15 +417,21 @@ mod tests {
 			inputs: vec![ethabi::EventParam {
 				name: "foo".into(),
 				kind: ethabi::ParamType::Address,
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}, ethabi::EventParam {
 				name: "bar".into(),
 				kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::String)),
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}, ethabi::EventParam {
 				name: "xyz".into(),
 				kind: ethabi::ParamType::Uint(256),
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}],
 			anonymous: false,
 		};
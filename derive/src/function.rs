@@ -5,7 +5,7 @@ use syn::export::Span;
 
 use super::{
 	input_names, template_param_type, rust_type, get_template_names, from_template_param, to_token,
-	to_ethabi_param_vec, get_output_kinds, from_token
+	to_ethabi_param_vec, get_output_kinds, from_token, to_state_mutability, to_rustdoc
 };
 
 struct TemplateParam {
@@ -37,6 +37,10 @@ struct Inputs {
 }
 
 struct Outputs {
+	/// Collects template params into vector, see `Inputs::tokenize`.
+	tokenize: Vec<TokenStream>,
+	/// Template params used to encode outputs.
+	template_params: Vec<TemplateParam>,
 	/// Decoding implementation.
 	implementation: TokenStream,
 	/// Decode result.
@@ -55,38 +59,57 @@ pub struct Function {
 	outputs: Outputs,
 	/// Constant function.
 	constant: bool,
+	/// Function state mutability.
+	state_mutability: TokenStream,
+	/// Rustdoc attributes built from NatSpec documentation.
+	docs: Vec<TokenStream>,
 }
 
-impl<'a> From<&'a ethabi::Function> for Function {
-	fn from(f: &'a ethabi::Function) -> Self {
-		// [param0, hello_world, param2]
-		let input_names = input_names(&f.inputs);
+/// Returns template params and the tokens built from them for given params.
+fn to_template_params(params: &[ethabi::Param]) -> (Vec<TemplateParam>, Vec<TokenStream>) {
+	// [param0, hello_world, param2]
+	let names = input_names(params);
+
+	// [T0: Into<Uint>, T1: Into<Bytes>, T2: IntoIterator<Item = U2>, U2 = Into<Uint>]
+	let declarations = params.iter().enumerate()
+		.map(|(index, param)| template_param_type(&param.kind, index));
 
-		// [T0: Into<Uint>, T1: Into<Bytes>, T2: IntoIterator<Item = U2>, U2 = Into<Uint>]
-		let declarations = f.inputs.iter().enumerate()
-			.map(|(index, param)| template_param_type(&param.kind, index));
+	// [Uint, Bytes, Vec<Uint>]
+	let kinds: Vec<_> = params
+		.iter()
+		.map(|param| rust_type(&param.kind))
+		.collect();
 
-		// [Uint, Bytes, Vec<Uint>]
-		let kinds: Vec<_> = f.inputs
-			.iter()
-			.map(|param| rust_type(&param.kind))
-			.collect();
+	// [T0, T1, T2]
+	let template_names: Vec<_> = get_template_names(&kinds);
 
-		// [T0, T1, T2]
-		let template_names: Vec<_> = get_template_names(&kinds);
+	// [param0: T0, hello_world: T1, param2: T2]
+	let definitions = names.iter().zip(template_names.iter())
+		.map(|(param_name, template_name)| quote! { #param_name: #template_name });
 
-		// [param0: T0, hello_world: T1, param2: T2]
-		let definitions = input_names.iter().zip(template_names.iter())
-			.map(|(param_name, template_name)| quote! { #param_name: #template_name });
+	let template_params = declarations.zip(definitions)
+		.map(|(declaration, definition)| TemplateParam { declaration, definition })
+		.collect();
 
-		let template_params = declarations.zip(definitions)
-			.map(|(declaration, definition)| TemplateParam { declaration, definition })
-			.collect();
+	// [Token::Uint(param0.into()), Token::Bytes(hello_world.into()), Token::Array(param2.into_iter().map(Into::into).collect())]
+	let tokenize = names.iter().zip(params.iter())
+		.map(|(param_name, param)| to_token(&from_template_param(&param.kind, &param_name), &param.kind))
+		.collect();
+
+	(template_params, tokenize)
+}
+
+impl<'a> From<&'a ethabi::Function> for Function {
+	fn from(f: &'a ethabi::Function) -> Self {
+		Function::new(f, None)
+	}
+}
 
-		// [Token::Uint(param0.into()), Token::Bytes(hello_world.into()), Token::Array(param2.into_iter().map(Into::into).collect())]
-		let tokenize: Vec<_> = input_names.iter().zip(f.inputs.iter())
-			.map(|(param_name, param)| to_token(&from_template_param(&param.kind, &param_name), &param.kind))
-			.collect();
+impl Function {
+	/// Creates function interface generator, documented with given NatSpec documentation.
+	pub fn new(f: &ethabi::Function, docs: Option<&ethabi::Documentation>) -> Self {
+		let (template_params, tokenize) = to_template_params(&f.inputs);
+		let (output_template_params, output_tokenize) = to_template_params(&f.outputs);
 
 		let output_result = get_output_kinds(&f.outputs);
 
@@ -125,16 +148,18 @@ impl<'a> From<&'a ethabi::Function> for Function {
 				recreate_quote: to_ethabi_param_vec(&f.inputs),
 			},
 			outputs: Outputs {
+				tokenize: output_tokenize,
+				template_params: output_template_params,
 				implementation: output_implementation,
 				result: output_result,
 				recreate_quote: to_ethabi_param_vec(&f.outputs),
 			},
 			constant: f.constant,
+			state_mutability: to_state_mutability(f.state_mutability),
+			docs: to_rustdoc(docs, &param_names(&f.inputs), &param_names(&f.outputs)),
 		}
 	}
-}
 
-impl Function {
 	/// Generates the interface for contract's function.
 	pub fn generate(&self) -> TokenStream {
 		let name = &self.name;
@@ -145,10 +170,16 @@ impl Function {
 		let recreate_inputs = &self.inputs.recreate_quote;
 		let recreate_outputs = &self.outputs.recreate_quote;
 		let constant = &self.constant;
+		let state_mutability = &self.state_mutability;
 		let outputs_result = &self.outputs.result;
 		let outputs_implementation = &self.outputs.implementation;
+		let output_tokenize = &self.outputs.tokenize;
+		let output_declarations: &Vec<_> = &self.outputs.template_params.iter().map(|i| &i.declaration).collect();
+		let output_definitions: &Vec<_> = &self.outputs.template_params.iter().map(|i| &i.definition).collect();
+		let docs = &self.docs;
 
 		quote! {
+			#(#docs)*
 			pub mod #module_name {
 				use ethabi;
 				use super::INTERNAL_ERR;
@@ -159,6 +190,7 @@ impl Function {
 						inputs: #recreate_inputs,
 						outputs: #recreate_outputs,
 						constant: #constant,
+						state_mutability: #state_mutability,
 					}
 				}
 
@@ -185,6 +217,13 @@ impl Function {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
+				/// Encodes function output.
+				pub fn encode_output<#(#output_declarations),*>(#(#output_definitions),*) -> ethabi::Bytes {
+					let f = function();
+					let tokens = vec![#(#output_tokenize),*];
+					f.encode_output(&tokens).expect(INTERNAL_ERR)
+				}
+
 				/// Encodes function output and creates a `Decoder` instance.
 				pub fn call<#(#declarations),*>(#(#definitions),*) -> (ethabi::Bytes, Decoder) {
 					let f = function();
@@ -196,6 +235,10 @@ impl Function {
 	}
 }
 
+fn param_names(params: &[ethabi::Param]) -> Vec<&str> {
+	params.iter().map(|param| param.name.as_str()).collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use ethabi;
@@ -208,6 +251,7 @@ mod tests {
 			inputs: vec![],
 			outputs: vec![],
 			constant: false,
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let f = Function::from(&ethabi_function);
@@ -223,6 +267,7 @@ mod tests {
 						inputs: vec![],
 						outputs: vec![],
 						constant: false,
+						state_mutability: ethabi::StateMutability::NonPayable,
 					}
 				}
 
@@ -250,6 +295,13 @@ mod tests {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
+				/// Encodes function output.
+				pub fn encode_output<>() -> ethabi::Bytes {
+					let f = function();
+					let tokens = vec![];
+					f.encode_output(&tokens).expect(INTERNAL_ERR)
+				}
+
 				/// Encodes function output and creates a `Decoder` instance.
 				pub fn call<>() -> (ethabi::Bytes, Decoder) {
 					let f = function();
@@ -270,6 +322,7 @@ mod tests {
 				ethabi::Param {
 					name: "foo".into(),
 					kind: ethabi::ParamType::Address,
+					internal_type: None,
 					components: vec![],
 				}
 			],
@@ -277,10 +330,12 @@ mod tests {
 				ethabi::Param {
 					name: "bar".into(),
 					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
 					components: vec![],
 				}
 			],
 			constant: false,
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let f = Function::from(&ethabi_function);
@@ -296,14 +351,17 @@ mod tests {
 						inputs: vec![ethabi::Param {
 							name: "foo".to_owned(),
 							kind: ethabi::ParamType::Address,
+							internal_type: None,
 							components: vec![],
 						}],
 						outputs: vec![ethabi::Param {
 							name: "bar".to_owned(),
 							kind: ethabi::ParamType::Uint(256usize),
+							internal_type: None,
 							components: vec![],
 						}],
 						constant: false,
+						state_mutability: ethabi::StateMutability::NonPayable,
 					}
 				}
 
@@ -331,6 +389,13 @@ mod tests {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
+				/// Encodes function output.
+				pub fn encode_output<T0: Into<ethabi::Uint> >(bar: T0) -> ethabi::Bytes {
+					let f = function();
+					let tokens = vec![ethabi::Token::Uint(bar.into())];
+					f.encode_output(&tokens).expect(INTERNAL_ERR)
+				}
+
 				/// Encodes function output and creates a `Decoder` instance.
 				pub fn call<T0: Into<ethabi::Address> >(foo: T0) -> (ethabi::Bytes, Decoder) {
 					let f = function();
@@ -351,11 +416,13 @@ mod tests {
 				ethabi::Param {
 					name: "foo".into(),
 					kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2),
+					internal_type: None,
 					components: vec![],
 				},
 				ethabi::Param {
 					name: "bar".into(),
 					kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256))),
+					internal_type: None,
 					components: vec![],
 				}
 			],
@@ -363,15 +430,18 @@ mod tests {
 				ethabi::Param {
 					name: "".into(),
 					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
 					components: vec![],
 				},
 				ethabi::Param {
 					name: "".into(),
 					kind: ethabi::ParamType::String,
+					internal_type: None,
 					components: vec![],
 				}
 			],
 			constant: false,
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let f = Function::from(&ethabi_function);
@@ -386,19 +456,28 @@ mod tests {
 						name: "multi".into(),
 						inputs: vec![ethabi::Param {
 							name: "foo".to_owned(),
-							kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2usize)
+							kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2usize),
+							internal_type: None,
+							components: vec![],
 						}, ethabi::Param {
 							name: "bar".to_owned(),
-							kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256usize)))
+							kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256usize))),
+							internal_type: None,
+							components: vec![],
 						}],
 						outputs: vec![ethabi::Param {
 							name: "".to_owned(),
-							kind: ethabi::ParamType::Uint(256usize)
+							kind: ethabi::ParamType::Uint(256usize),
+							internal_type: None,
+							components: vec![],
 						}, ethabi::Param {
 							name: "".to_owned(),
-							kind: ethabi::ParamType::String
+							kind: ethabi::ParamType::String,
+							internal_type: None,
+							components: vec![],
 						}],
 						constant: false,
+						state_mutability: ethabi::StateMutability::NonPayable,
 					}
 				}
 
@@ -432,6 +511,13 @@ mod tests {
 					ethabi::FunctionOutputDecoder::decode(&Decoder(function()), output)
 				}
 
+				/// Encodes function output.
+				pub fn encode_output<T0: Into<ethabi::Uint>, T1: Into<String> >(param0: T0, param1: T1) -> ethabi::Bytes {
+					let f = function();
+					let tokens = vec![ethabi::Token::Uint(param0.into()), ethabi::Token::String(param1.into())];
+					f.encode_output(&tokens).expect(INTERNAL_ERR)
+				}
+
 				/// Encodes function output and creates a `Decoder` instance.
 				pub fn call<T0: Into<[U0; 2usize]>, U0: Into<ethabi::Address>, T1: IntoIterator<Item = U1>, U1: Into<ethabi::Uint> >(foo: T0, bar: T1) -> (ethabi::Bytes, Decoder) {
 					let f = function();
@@ -16,8 +16,9 @@ mod function;
 use std::{env, fs};
 use std::path::PathBuf;
 use heck::SnakeCase;
+use proc_macro2::{Delimiter, Group, TokenTree};
 use syn::export::Span;
-use ethabi::{Result, ResultExt, Contract, Param, ParamType};
+use ethabi::{Result, ResultExt, Artifact, Documentation, Param, ParamType, StateMutability};
 
 const ERROR_MSG: &str = "`derive(EthabiContract)` failed";
 
@@ -34,14 +35,18 @@ fn impl_ethabi_derive(ast: &syn::DeriveInput) -> Result<proc_macro2::TokenStream
 	let normalized_path = normalize_path(&path)?;
 	let source_file = fs::File::open(&normalized_path)
 		.chain_err(|| format!("Cannot load contract abi from `{}`", normalized_path.display()))?;
-	let contract = Contract::load(source_file)?;
-	let c = contract::Contract::from(&contract);
+	let artifact = match find_option(&options, "name")? {
+		Some(name) => Artifact::load_by_name(source_file, &name)?,
+		None => Artifact::load(source_file)?,
+	};
+	let c = contract::Contract::from(&artifact.contract);
 	Ok(c.generate())
 }
 
 fn get_options(attrs: &[syn::Attribute], name: &str) -> Result<Vec<syn::NestedMeta>> {
 	let options = attrs.iter()
-		.flat_map(syn::Attribute::interpret_meta)
+		.map(|attr| syn::Attribute { tts: flatten_groups(attr.tts.clone()), ..attr.clone() })
+		.flat_map(|attr| attr.interpret_meta())
 		.find(|meta| meta.name() == name);
 
 
@@ -51,15 +56,36 @@ fn get_options(attrs: &[syn::Attribute], name: &str) -> Result<Vec<syn::NestedMe
 	}
 }
 
+/// Unwraps invisible groups, which wrap `expr` fragments passed by `use_contract!`
+/// and which `interpret_meta` does not accept as literals.
+fn flatten_groups(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	tokens.into_iter()
+		.flat_map(|token| match token {
+			TokenTree::Group(ref group) if group.delimiter() == Delimiter::None => {
+				flatten_groups(group.stream()).into_iter().collect()
+			},
+			TokenTree::Group(ref group) => {
+				let mut flattened = Group::new(group.delimiter(), flatten_groups(group.stream()));
+				flattened.set_span(group.span());
+				vec![TokenTree::Group(flattened)]
+			},
+			other => vec![other],
+		})
+		.collect()
+}
+
 fn get_option(options: &[syn::NestedMeta], name: &str) -> Result<String> {
+	find_option(options, name)?.chain_err(|| format!("Expected to find option {}", name))
+}
+
+fn find_option(options: &[syn::NestedMeta], name: &str) -> Result<Option<String>> {
 	let item = options.iter()
 		.flat_map(|nested| match *nested {
 			syn::NestedMeta::Meta(ref meta) => Some(meta),
 			_ => None,
 		})
-		.find(|meta| meta.name() == name)
-		.chain_err(|| format!("Expected to find option {}", name))?;
-	str_value_of_meta_item(item, name)
+		.find(|meta| meta.name() == name);
+	item.map(|item| str_value_of_meta_item(item, name)).map_or(Ok(None), |value| value.map(Some))
 }
 
 fn str_value_of_meta_item(item: &syn::Meta, name: &str) -> Result<String> {
@@ -96,7 +122,23 @@ fn to_syntax_string(param_type: &ethabi::ParamType) -> proc_macro2::TokenStream
 		ParamType::FixedArray(ref param_type, ref x) => {
 			let param_type_quote = to_syntax_string(param_type);
 			quote! { ethabi::ParamType::FixedArray(Box::new(#param_type_quote), #x) }
-		}
+		},
+		ParamType::Function => quote! { ethabi::ParamType::Function },
+		ParamType::Fixed(x, d) => quote! { ethabi::ParamType::Fixed(#x, #d) },
+		ParamType::UFixed(x, d) => quote! { ethabi::ParamType::UFixed(#x, #d) },
+		ParamType::Tuple(ref param_types) => {
+			let param_type_quotes = param_types.iter().map(to_syntax_string);
+			quote! { ethabi::ParamType::Tuple(vec![#(#param_type_quotes),*]) }
+		},
+	}
+}
+
+fn to_state_mutability(state_mutability: StateMutability) -> proc_macro2::TokenStream {
+	match state_mutability {
+		StateMutability::Pure => quote! { ethabi::StateMutability::Pure },
+		StateMutability::View => quote! { ethabi::StateMutability::View },
+		StateMutability::NonPayable => quote! { ethabi::StateMutability::NonPayable },
+		StateMutability::Payable => quote! { ethabi::StateMutability::Payable },
 	}
 }
 
@@ -106,10 +148,17 @@ fn to_ethabi_param_vec<'a, P: 'a>(params: P) -> proc_macro2::TokenStream
 	let p = params.into_iter().map(|x| {
 		let name = &x.name;
 		let kind = to_syntax_string(&x.kind);
+		let internal_type = match x.internal_type {
+			Some(ref internal_type) => quote! { Some(#internal_type.to_owned()) },
+			None => quote! { None },
+		};
+		let components = to_ethabi_param_vec(&x.components);
 		quote! {
 			ethabi::Param {
 				name: #name.to_owned(),
-				kind: #kind
+				kind: #kind,
+				internal_type: #internal_type,
+				components: #components,
 			}
 		}
 	}).collect::<Vec<_>>();
@@ -134,7 +183,14 @@ fn rust_type(input: &ParamType) -> proc_macro2::TokenStream {
 		ParamType::FixedArray(ref kind, size) => {
 			let t = rust_type(&*kind);
 			quote! { [#t, #size] }
-		}
+		},
+		ParamType::Function => quote! { (ethabi::Address, [u8; 4]) },
+		ParamType::Fixed(_, _) => quote! { ethabi::Int },
+		ParamType::UFixed(_, _) => quote! { ethabi::Uint },
+		ParamType::Tuple(ref kinds) => {
+			let t = kinds.iter().map(rust_type);
+			quote! { (#(#t,)*) }
+		},
 	}
 }
 
@@ -161,7 +217,14 @@ fn template_param_type(input: &ParamType, index: usize) -> proc_macro2::TokenStr
 			quote! {
 				#t_ident: Into<[#u_ident; #size]>, #u_ident: Into<#t>
 			}
-		}
+		},
+		ParamType::Function => quote! { #t_ident: Into<(ethabi::Address, [u8; 4])> },
+		ParamType::Fixed(_, _) => quote! { #t_ident: Into<ethabi::Int> },
+		ParamType::UFixed(_, _) => quote! { #t_ident: Into<ethabi::Uint> },
+		ParamType::Tuple(_) => {
+			let t = rust_type(input);
+			quote! { #t_ident: Into<#t> }
+		},
 	}
 }
 
@@ -169,6 +232,10 @@ fn from_template_param(input: &ParamType, name: &syn::Ident) -> proc_macro2::Tok
 	match *input {
 		ParamType::Array(_) => quote! { #name.into_iter().map(Into::into).collect::<Vec<_>>() },
 		ParamType::FixedArray(_, _) => quote! { (Box::new(#name.into()) as Box<[_]>).into_vec().into_iter().map(Into::into).collect::<Vec<_>>() },
+		ParamType::Tuple(_) => {
+			let t = rust_type(input);
+			quote! { Into::<#t>::into(#name) }
+		},
 		_ => quote! {#name.into() },
 	}
 }
@@ -204,6 +271,22 @@ fn to_token(name: &proc_macro2::TokenStream, kind: &ParamType) -> proc_macro2::T
 				}
 			}
 		},
+		ParamType::Function => quote! { ethabi::Token::Function(#name.0, #name.1) },
+		ParamType::Fixed(_, _) => quote! { ethabi::Token::Fixed(#name) },
+		ParamType::UFixed(_, _) => quote! { ethabi::Token::UFixed(#name) },
+		ParamType::Tuple(ref kinds) => {
+			let tuple = quote! { tuple };
+			let inner = kinds.iter().enumerate().map(|(index, kind)| {
+				let index = syn::Index::from(index);
+				to_token(&quote! { #tuple.#index }, kind)
+			});
+			quote! {
+				{
+					let #tuple = #name;
+					ethabi::Token::Tuple(vec![#(#inner),*])
+				}
+			}
+		},
 	}
 }
 
@@ -255,6 +338,27 @@ fn from_token(kind: &ParamType, token: &proc_macro2::TokenStream) -> proc_macro2
 				}
 			}
 		},
+		ParamType::Function => quote! { #token.to_function().expect(INTERNAL_ERR) },
+		ParamType::Fixed(_, _) => quote! { #token.to_fixed().expect(INTERNAL_ERR) },
+		ParamType::UFixed(_, _) => quote! { #token.to_ufixed().expect(INTERNAL_ERR) },
+		ParamType::Tuple(ref kinds) => {
+			let inner = quote! { inner };
+			let inner_loop = kinds.iter().map(|kind| {
+				let from_inner = from_token(kind, &inner);
+				quote! {
+					{
+						let #inner = iter.next().expect(INTERNAL_ERR);
+						#from_inner
+					}
+				}
+			});
+			quote! {
+				{
+					let mut iter = #token.to_tuple().expect(INTERNAL_ERR).into_iter();
+					(#(#inner_loop,)*)
+				}
+			}
+		},
 	}
 }
 
@@ -293,6 +397,41 @@ fn get_output_kinds(outputs: &[Param]) -> proc_macro2::TokenStream {
 	}
 }
 
+/// Converts NatSpec documentation of an entry to rustdoc attributes.
+fn to_rustdoc(docs: Option<&Documentation>, inputs: &[&str], outputs: &[&str]) -> Vec<proc_macro2::TokenStream> {
+	let docs = match docs {
+		Some(docs) => docs,
+		None => return vec![],
+	};
+
+	let mut paragraphs: Vec<String> = docs.notice.iter().chain(docs.details.iter()).cloned().collect();
+
+	let params: Vec<_> = inputs.iter()
+		.filter_map(|name| docs.param(name).map(|doc| format!("- `{}`: {}", name, doc)))
+		.collect();
+	if !params.is_empty() {
+		paragraphs.push(format!("# Params\n\n{}", params.join("\n")));
+	}
+
+	let returns: Vec<_> = outputs.iter().enumerate()
+		.filter_map(|(index, name)| docs.output(index, name).map(|doc| if name.is_empty() {
+			format!("- {}", doc)
+		} else {
+			format!("- `{}`: {}", name, doc)
+		}))
+		.collect();
+	if !returns.is_empty() {
+		paragraphs.push(format!("# Returns\n\n{}", returns.join("\n")));
+	}
+
+	paragraphs.join("\n\n").lines()
+		.map(|line| {
+			let line = format!(" {}", line);
+			quote! { #[doc = #line] }
+		})
+		.collect()
+}
+
 /// Convert input into a rust variable name.
 ///
 /// Avoid using keywords by escaping them.
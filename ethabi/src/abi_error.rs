@@ -0,0 +1,110 @@
+//! Contract custom error.
+
+use human_readable;
+use signature::{short_signature, signature_string};
+use {decode, encode, Bytes, ErrorKind, NamedToken, Param, ParamType, Result, Token};
+
+/// Contract custom error specification, eg. `error InsufficientBalance(uint256 available, uint256 required)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbiError {
+	/// Error name.
+	pub name: String,
+	/// Error input.
+	pub inputs: Vec<Param>,
+}
+
+impl AbiError {
+	/// Returns all input params of given error.
+	fn param_types(&self) -> Vec<ParamType> {
+		self.inputs.iter().map(|p| p.true_type()).collect()
+	}
+
+	/// Returns canonical error signature, eg. `InsufficientBalance(uint256,uint256)`.
+	pub fn signature(&self) -> String {
+		signature_string(&self.name, &self.param_types())
+	}
+
+	/// Returns 4-byte error selector.
+	pub fn selector(&self) -> [u8; 4] {
+		short_signature(&self.name, &self.param_types())
+	}
+
+	/// Returns human-readable error, eg. `error InsufficientBalance(uint256 available, uint256 required)`.
+	pub fn human_readable(&self) -> String {
+		human_readable::format_error(self)
+	}
+
+	/// Prepares ABI encoded revert data with given params.
+	pub fn encode(&self, tokens: &[Token]) -> Result<Bytes> {
+		if !Token::types_check(tokens, &self.param_types()) {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		let signed = self.selector().to_vec();
+		let encoded = encode(tokens);
+		Ok(signed.into_iter().chain(encoded.into_iter()).collect())
+	}
+
+	/// Parses ABI encoded revert data, including the selector, to list of tokens.
+	pub fn decode(&self, data: &[u8]) -> Result<Vec<Token>> {
+		if data.len() < 4 || data[..4] != self.selector() {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		decode(&self.param_types(), &data[4..])
+	}
+
+	/// Parses ABI encoded revert data, including the selector, to list of named tokens.
+	pub fn decode_named(&self, data: &[u8]) -> Result<Vec<NamedToken>> {
+		let tokens = self.decode(data)?;
+		Ok(NamedToken::from_params(&self.inputs, tokens))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use {AbiError, NamedToken, Token};
+
+	fn insufficient_balance() -> AbiError {
+		let s = r#"{
+			"name": "InsufficientBalance",
+			"inputs": [
+				{ "name": "available", "type": "uint256" },
+				{ "name": "required", "type": "uint256" }
+			]
+		}"#;
+
+		serde_json::from_str(s).unwrap()
+	}
+
+	#[test]
+	fn abi_error_signature() {
+		let error = insufficient_balance();
+		assert_eq!(error.signature(), "InsufficientBalance(uint256,uint256)");
+		assert_eq!(error.selector(), hex!("cf479181"));
+		assert_eq!(error.human_readable(), "error InsufficientBalance(uint256 available, uint256 required)");
+	}
+
+	#[test]
+	fn abi_error_encode_decode() {
+		let error = insufficient_balance();
+		let tokens = vec![Token::Uint(1.into()), Token::Uint(2.into())];
+		let encoded = error.encode(&tokens).unwrap();
+
+		let expected = hex!("
+			cf479181
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000002
+		").to_vec();
+		assert_eq!(encoded, expected);
+		assert_eq!(error.decode(&encoded).unwrap(), tokens);
+		assert_eq!(error.decode_named(&encoded).unwrap(), vec![
+			NamedToken { name: "available".to_owned(), value: Token::Uint(1.into()) },
+			NamedToken { name: "required".to_owned(), value: Token::Uint(2.into()) },
+		]);
+
+		assert!(error.decode(&encoded[1..]).is_err());
+		assert!(error.encode(&[Token::Bool(true)]).is_err());
+	}
+}
@@ -0,0 +1,338 @@
+//! Contracts loaded from compiler and framework artifacts.
+//!
+//! Supported formats are:
+//!
+//! - bare json abi,
+//! - solc `--combined-json` output,
+//! - solc standard json output,
+//! - Hardhat, Foundry and Truffle artifacts.
+
+use std::collections::BTreeMap;
+use std::io;
+use hex::FromHex;
+use serde_json::{self, Map, Value};
+use natspec;
+use {Bytes, Contract, Result};
+
+/// Position of a library address placeholder in bytecode, in bytes.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LinkReference {
+	/// Offset of the placeholder.
+	pub start: usize,
+	/// Length of the placeholder.
+	pub length: usize,
+}
+
+/// Library placeholders in bytecode, by source file and library name.
+pub type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<LinkReference>>>;
+
+/// Contract bytecode, possibly containing placeholders of libraries that are not linked yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bytecode {
+	/// Hex encoded bytecode, without `0x` prefix.
+	pub object: String,
+	/// Library placeholders in the bytecode.
+	pub link_references: LinkReferences,
+}
+
+impl Bytecode {
+	/// Returns true if the bytecode has no library placeholders.
+	pub fn is_linked(&self) -> bool {
+		!self.object.contains("__")
+	}
+
+	/// Returns decoded bytecode. Fails if the bytecode is not linked.
+	pub fn to_bytes(&self) -> Result<Bytes> {
+		self.object.from_hex().map_err(From::from)
+	}
+}
+
+/// Contract loaded from an artifact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artifact {
+	/// Contract name, if known.
+	pub name: Option<String>,
+	/// Contract abi.
+	pub contract: Contract,
+	/// Creation bytecode.
+	pub bytecode: Option<Bytecode>,
+	/// Runtime bytecode.
+	pub deployed_bytecode: Option<Bytecode>,
+}
+
+impl Artifact {
+	/// Loads all contracts from an artifact.
+	pub fn load_all<T: io::Read>(reader: T) -> Result<Vec<Artifact>> {
+		let value: Value = serde_json::from_reader(reader)?;
+		match value {
+			Value::Array(_) => Ok(vec![Artifact {
+				name: None,
+				contract: serde_json::from_value(value)?,
+				bytecode: None,
+				deployed_bytecode: None,
+			}]),
+			Value::Object(ref object) if object.contains_key("abi") => Ok(vec![artifact(None, object)?]),
+			Value::Object(ref object) => match object.get("contracts") {
+				Some(&Value::Object(ref contracts)) => compiler_output(contracts),
+				_ => Err("Unknown artifact format, expected json abi or an object with `abi` or `contracts` field".into()),
+			},
+			_ => Err("Unknown artifact format, expected json abi or an object with `abi` or `contracts` field".into()),
+		}
+	}
+
+	/// Loads the only contract of an artifact.
+	///
+	/// Fails if the artifact contains more than one contract, use `load_by_name` or `load_all` instead.
+	pub fn load<T: io::Read>(reader: T) -> Result<Artifact> {
+		let mut artifacts = Artifact::load_all(reader)?;
+		match artifacts.len() {
+			1 => Ok(artifacts.remove(0)),
+			0 => Err("Expected artifact with one contract, found none".into()),
+			_ => Err(format!("Expected artifact with one contract, found {}, select one by name", names(&artifacts)).into()),
+		}
+	}
+
+	/// Loads contract with given name, eg. from solc `--combined-json` output.
+	///
+	/// Fails if there is no such contract, or if more than one contract has the name.
+	pub fn load_by_name<T: io::Read>(reader: T, name: &str) -> Result<Artifact> {
+		let artifacts = Artifact::load_all(reader)?;
+		let all = names(&artifacts);
+		let mut found = artifacts.into_iter()
+			.filter(|artifact| artifact.name.as_ref().map_or(false, |n| n == name))
+			.collect::<Vec<_>>();
+		match found.len() {
+			1 => Ok(found.remove(0)),
+			0 => Err(format!("Cannot find contract `{}`, found {}", name, all).into()),
+			_ => Err(format!("Found more than one contract named `{}`", name).into()),
+		}
+	}
+}
+
+fn names(artifacts: &[Artifact]) -> String {
+	artifacts.iter()
+		.map(|artifact| format!("`{}`", artifact.name.as_ref().map_or("<unnamed>", String::as_str)))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+/// Reads `contracts` field of solc `--combined-json` or standard json output.
+fn compiler_output(contracts: &Map<String, Value>) -> Result<Vec<Artifact>> {
+	let mut result = vec![];
+	for (key, entry) in contracts {
+		let entry = as_object(entry, key)?;
+		if entry.contains_key("abi") || entry.contains_key("bin") {
+			// combined json, keyed by `path:Name`
+			let name = key.rsplit(':').next().unwrap_or(key);
+			result.push(artifact(Some(name), entry)?);
+		} else {
+			// standard json, keyed by path and then by name
+			for (name, contract) in entry {
+				result.push(artifact(Some(name), as_object(contract, name)?)?);
+			}
+		}
+	}
+	Ok(result)
+}
+
+fn artifact(name: Option<&str>, object: &Map<String, Value>) -> Result<Artifact> {
+	let name = name.or_else(|| object.get("contractName").and_then(Value::as_str)).map(ToOwned::to_owned);
+	let mut contract: Contract = match object.get("abi") {
+		// solc < 0.8 writes the abi in combined json as a string
+		Some(&Value::String(ref abi)) => serde_json::from_str(abi)?,
+		Some(abi) => serde_json::from_value(abi.clone())?,
+		None => return Err(format!("Missing abi of contract `{}`", name.unwrap_or_default()).into()),
+	};
+
+	// natspec is written next to the abi, or in the metadata output in foundry artifacts
+	let metadata = match object.get("metadata") {
+		Some(&Value::String(ref metadata)) => serde_json::from_str(metadata).ok(),
+		metadata => metadata.cloned(),
+	};
+	let output = metadata.as_ref().and_then(|metadata| metadata.get("output"));
+	let userdoc = object.get("userdoc").or_else(|| output.and_then(|output| output.get("userdoc")));
+	let devdoc = object.get("devdoc").or_else(|| output.and_then(|output| output.get("devdoc")));
+	if userdoc.is_some() || devdoc.is_some() {
+		contract.docs = natspec::parse(userdoc.cloned().unwrap_or(Value::Null), devdoc.cloned().unwrap_or(Value::Null))?;
+	}
+
+	let evm = object.get("evm");
+	let bytecode = object.get("bytecode")
+		.or_else(|| object.get("bin"))
+		.or_else(|| evm.and_then(|evm| evm.get("bytecode")));
+	let deployed_bytecode = object.get("deployedBytecode")
+		.or_else(|| object.get("bin-runtime"))
+		.or_else(|| evm.and_then(|evm| evm.get("deployedBytecode")));
+
+	Ok(Artifact {
+		name,
+		contract,
+		bytecode: read_bytecode(bytecode, object.get("linkReferences"))?,
+		deployed_bytecode: read_bytecode(deployed_bytecode, object.get("deployedLinkReferences"))?,
+	})
+}
+
+/// Reads bytecode written either as a hex string or as an object with `object` and `linkReferences` fields.
+fn read_bytecode(value: Option<&Value>, link_references: Option<&Value>) -> Result<Option<Bytecode>> {
+	let (object, link_references) = match value {
+		Some(&Value::String(ref object)) => (object, link_references),
+		Some(&Value::Object(ref bytecode)) => match bytecode.get("object") {
+			Some(&Value::String(ref object)) => (object, bytecode.get("linkReferences")),
+			_ => return Ok(None),
+		},
+		_ => return Ok(None),
+	};
+
+	let object = object.trim_start_matches("0x");
+	if object.is_empty() {
+		return Ok(None);
+	}
+
+	let link_references = match link_references {
+		Some(link_references) => serde_json::from_value(link_references.clone())?,
+		None => LinkReferences::new(),
+	};
+
+	Ok(Some(Bytecode { object: object.to_owned(), link_references }))
+}
+
+fn as_object<'a>(value: &'a Value, key: &str) -> Result<&'a Map<String, Value>> {
+	value.as_object().ok_or_else(|| format!("Expected `{}` to be an object", key).into())
+}
+
+#[cfg(test)]
+mod tests {
+	use Entry;
+	use super::{Artifact, LinkReference};
+
+	const ABI: &str = r#"[{ "type": "function", "name": "foo", "inputs": [], "outputs": [] }]"#;
+
+	#[test]
+	fn load_bare_abi() {
+		let artifact = Artifact::load(ABI.as_bytes()).unwrap();
+		assert_eq!(artifact.name, None);
+		assert!(artifact.contract.function("foo").is_ok());
+		assert_eq!(artifact.bytecode, None);
+	}
+
+	#[test]
+	fn load_hardhat_artifact() {
+		let json = format!(r#"{{
+			"_format": "hh-sol-artifact-1",
+			"contractName": "Foo",
+			"abi": {},
+			"bytecode": "0x6080__$2a6e1d3e9f0b9f5e8a1c7c0f8c3e1d4b6a$__00",
+			"deployedBytecode": "0x6080",
+			"linkReferences": {{ "contracts/Lib.sol": {{ "Lib": [{{ "start": 2, "length": 20 }}] }} }},
+			"deployedLinkReferences": {{}}
+		}}"#, ABI);
+
+		let artifact = Artifact::load(json.as_bytes()).unwrap();
+		assert_eq!(artifact.name, Some("Foo".to_owned()));
+		assert!(artifact.contract.function("foo").is_ok());
+
+		let bytecode = artifact.bytecode.unwrap();
+		assert!(!bytecode.is_linked());
+		assert!(bytecode.to_bytes().is_err());
+		assert_eq!(bytecode.link_references["contracts/Lib.sol"]["Lib"], vec![LinkReference { start: 2, length: 20 }]);
+
+		let deployed = artifact.deployed_bytecode.unwrap();
+		assert!(deployed.is_linked());
+		assert_eq!(deployed.to_bytes().unwrap(), vec![0x60, 0x80]);
+	}
+
+	#[test]
+	fn load_foundry_artifact() {
+		let json = format!(r#"{{
+			"abi": {},
+			"bytecode": {{ "object": "0x6080", "linkReferences": {{}} }},
+			"deployedBytecode": {{ "object": "0x", "linkReferences": {{}} }},
+			"metadata": {{
+				"output": {{
+					"abi": {},
+					"userdoc": {{ "kind": "user", "methods": {{ "foo()": {{ "notice": "Does foo" }} }} }},
+					"devdoc": {{ "kind": "dev", "methods": {{}} }}
+				}}
+			}}
+		}}"#, ABI, ABI);
+
+		let artifact = Artifact::load(json.as_bytes()).unwrap();
+		assert_eq!(artifact.bytecode.unwrap().object, "6080");
+		assert_eq!(artifact.deployed_bytecode, None);
+		let docs = artifact.contract.docs(&Entry::Function("foo()".to_owned())).unwrap();
+		assert_eq!(docs.notice, Some("Does foo".to_owned()));
+	}
+
+	#[test]
+	fn load_truffle_artifact() {
+		let artifact = Artifact::load(include_str!("../../res/truffle.artifact.json").as_bytes()).unwrap();
+		assert_eq!(artifact.name, Some("MetaCoin".to_owned()));
+		assert!(artifact.contract.constructor().is_some());
+		assert_eq!(artifact.contract.functions().map(|f| f.name.as_str()).collect::<Vec<_>>(), vec!["sendCoin", "getBalanceInEth", "getBalance"]);
+		assert!(artifact.contract.event("Transfer").is_ok());
+
+		// truffle links libraries by name instead of writing link references
+		let bytecode = artifact.bytecode.unwrap();
+		assert!(!bytecode.is_linked());
+		assert!(bytecode.link_references.is_empty());
+
+		let docs = artifact.contract.docs(&Entry::Function("sendCoin(address,uint256)".to_owned())).unwrap();
+		assert_eq!(docs.notice, Some("Sends `amount` coins to `receiver`.".to_owned()));
+	}
+
+	#[test]
+	fn load_combined_json() {
+		let json = format!(r#"{{
+			"contracts": {{
+				"Foo.sol:Foo": {{ "abi": {:?}, "bin": "6080", "bin-runtime": "6040" }},
+				"Foo.sol:IFoo": {{ "abi": {}, "bin": "", "bin-runtime": "" }}
+			}},
+			"version": "0.7.6"
+		}}"#, ABI, ABI);
+
+		let artifacts = Artifact::load_all(json.as_bytes()).unwrap();
+		assert_eq!(artifacts.len(), 2);
+		assert_eq!(artifacts[0].name, Some("Foo".to_owned()));
+		assert!(artifacts[0].contract.function("foo").is_ok());
+		assert_eq!(artifacts[0].deployed_bytecode.as_ref().unwrap().object, "6040");
+		assert_eq!(artifacts[1].name, Some("IFoo".to_owned()));
+		assert_eq!(artifacts[1].bytecode, None);
+
+		assert!(Artifact::load(json.as_bytes()).is_err());
+		assert_eq!(Artifact::load_by_name(json.as_bytes(), "IFoo").unwrap(), artifacts[1]);
+		assert!(Artifact::load_by_name(json.as_bytes(), "Bar").is_err());
+	}
+
+	#[test]
+	fn load_standard_json() {
+		let json = format!(r#"{{
+			"contracts": {{
+				"Foo.sol": {{
+					"Foo": {{
+						"abi": {},
+						"evm": {{
+							"bytecode": {{
+								"object": "6080",
+								"linkReferences": {{ "Lib.sol": {{ "Lib": [{{ "start": 0, "length": 20 }}] }} }}
+							}},
+							"deployedBytecode": {{ "object": "6040", "linkReferences": {{}} }}
+						}}
+					}}
+				}}
+			}},
+			"sources": {{}}
+		}}"#, ABI);
+
+		let artifact = Artifact::load(json.as_bytes()).unwrap();
+		assert_eq!(artifact.name, Some("Foo".to_owned()));
+		assert_eq!(artifact.bytecode.unwrap().link_references["Lib.sol"]["Lib"].len(), 1);
+		assert_eq!(artifact.deployed_bytecode.unwrap().object, "6040");
+	}
+
+	#[test]
+	fn load_invalid_artifact() {
+		assert!(Artifact::load(&b"{}"[..]).is_err());
+		assert!(Artifact::load(&b"\"abi\""[..]).is_err());
+		assert!(Artifact::load(&br#"{ "contracts": { "Foo.sol": { "Foo": { "evm": {} } } } }"#[..]).is_err());
+	}
+}
@@ -0,0 +1,168 @@
+//! Selector and topic collisions between several contracts, eg. a proxy and its implementation
+//! or facets of an EIP-2535 diamond.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use hex::ToHex;
+use param_type::Writer;
+use {Contract, Event, Hash};
+
+/// Selector or topic shared by several entries.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Clash {
+	/// 4-byte function selector.
+	Selector([u8; 4]),
+	/// Event signature, ie. the first topic of its logs.
+	Topic(Hash),
+}
+
+impl fmt::Display for Clash {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Clash::Selector(ref selector) => write!(f, "selector 0x{}", selector.to_hex::<String>()),
+			Clash::Topic(ref topic) => write!(f, "topic 0x{:x}", topic),
+		}
+	}
+}
+
+/// Function or event involved in a collision.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionEntry {
+	/// Name of the contract.
+	pub contract: String,
+	/// Canonical signature of the function, or of the event with its indexed params and anonymity
+	/// marked, eg. `Transfer(address indexed,address indexed,uint256)`.
+	pub signature: String,
+}
+
+/// Entries sharing the same selector or topic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collision {
+	/// Shared selector or topic.
+	pub clash: Clash,
+	/// Colliding entries, in the order of given contracts.
+	pub entries: Vec<CollisionEntry>,
+}
+
+impl fmt::Display for Collision {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let entries = self.entries.iter()
+			.map(|e| format!("{}.{}", e.contract, e.signature))
+			.collect::<Vec<_>>()
+			.join(", ");
+		write!(f, "{}: {}", self.clash, entries)
+	}
+}
+
+/// Finds selectors and topics shared between functions and events of given named contracts.
+///
+/// Every function sharing a selector with another function is reported, even if both have
+/// the same signature, since only one of them can be called. Events are reported only if their
+/// layouts differ, eg. ERC20 and ERC721 `Transfer` index different params under the same topic,
+/// as the same event emitted by several contracts is decoded the same way.
+pub fn find_collisions<'a, I>(contracts: I) -> Vec<Collision> where I: IntoIterator<Item = (&'a str, &'a Contract)> {
+	let mut selectors: BTreeMap<Clash, Vec<CollisionEntry>> = BTreeMap::new();
+	let mut topics: BTreeMap<Clash, Vec<CollisionEntry>> = BTreeMap::new();
+
+	for (name, contract) in contracts {
+		let mut functions: Vec<_> = contract.functions().collect();
+		functions.sort_by_key(|f| f.signature());
+		for function in functions {
+			selectors.entry(Clash::Selector(function.selector()))
+				.or_default()
+				.push(CollisionEntry { contract: name.to_owned(), signature: function.signature() });
+		}
+
+		let mut events: Vec<_> = contract.events().collect();
+		events.sort_by_key(|e| layout(e));
+		for event in events {
+			let entries = topics.entry(Clash::Topic(event.signature())).or_default();
+			let signature = layout(event);
+			if entries.iter().all(|e| e.signature != signature) {
+				entries.push(CollisionEntry { contract: name.to_owned(), signature });
+			}
+		}
+	}
+
+	selectors.into_iter()
+		.chain(topics.into_iter())
+		.filter(|&(_, ref entries)| entries.len() > 1)
+		.map(|(clash, entries)| Collision { clash, entries })
+		.collect()
+}
+
+/// Returns event signature with indexed params and anonymity marked.
+fn layout(event: &Event) -> String {
+	let params = event.inputs.iter()
+		.map(|p| if p.indexed { format!("{} indexed", Writer::write(&p.kind)) } else { Writer::write(&p.kind) })
+		.collect::<Vec<_>>()
+		.join(",");
+	let anonymous = if event.anonymous { " anonymous" } else { "" };
+	format!("{}({}){}", event.name, params, anonymous)
+}
+
+#[cfg(test)]
+mod tests {
+	use Contract;
+	use super::{find_collisions, Clash, Collision, CollisionEntry};
+
+	fn entry(contract: &str, signature: &str) -> CollisionEntry {
+		CollisionEntry { contract: contract.to_owned(), signature: signature.to_owned() }
+	}
+
+	#[test]
+	fn find_selector_collisions() {
+		let proxy = Contract::from_human_readable(&[
+			"function upgradeTo(address implementation)",
+			"function burn(uint256 amount)",
+			"event Upgraded(address indexed implementation)",
+		]).unwrap();
+		let implementation = Contract::from_human_readable(&[
+			"function collate_propagate_storage(bytes16)",
+			"function upgradeTo(address implementation)",
+			"function mint(uint256 amount)",
+			"event Upgraded(address indexed implementation)",
+		]).unwrap();
+
+		let collisions = find_collisions(vec![("Proxy", &proxy), ("Implementation", &implementation)]);
+		assert_eq!(collisions, vec![
+			Collision {
+				clash: Clash::Selector(hex!("3659cfe6")),
+				entries: vec![entry("Proxy", "upgradeTo(address)"), entry("Implementation", "upgradeTo(address)")],
+			},
+			Collision {
+				clash: Clash::Selector(hex!("42966c68")),
+				entries: vec![entry("Proxy", "burn(uint256)"), entry("Implementation", "collate_propagate_storage(bytes16)")],
+			},
+		]);
+		assert_eq!(collisions[1].to_string(), "selector 0x42966c68: Proxy.burn(uint256), Implementation.collate_propagate_storage(bytes16)");
+
+		assert!(find_collisions(vec![("Proxy", &proxy)]).is_empty());
+	}
+
+	#[test]
+	fn find_topic_collisions() {
+		let erc20 = Contract::from_human_readable(&[
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"event Approval(address indexed owner, address indexed spender, uint256 value)",
+		]).unwrap();
+		let erc721 = Contract::from_human_readable(&[
+			"event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+			"event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId)",
+		]).unwrap();
+		let same = Contract::from_human_readable(&["event Transfer(address indexed src, address indexed dst, uint256 wad)"]).unwrap();
+
+		let collisions = find_collisions(vec![("ERC20", &erc20), ("ERC721", &erc721), ("WETH", &same)]);
+		assert_eq!(collisions.len(), 2);
+		let transfer = collisions.iter()
+			.find(|c| c.entries[0].signature.starts_with("Transfer"))
+			.unwrap();
+		assert_eq!(transfer.entries, vec![
+			entry("ERC20", "Transfer(address indexed,address indexed,uint256)"),
+			entry("ERC721", "Transfer(address indexed,address indexed,uint256 indexed)"),
+		]);
+		assert_eq!(transfer.clash.to_string(), "topic 0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+		assert!(find_collisions(vec![("ERC20", &erc20), ("WETH", &same)]).is_empty());
+	}
+}
@@ -1,29 +1,116 @@
 //! Contract constructor call builder.
-use {Param, Result, ErrorKind, Token, ParamType, encode, Bytes};
+use human_readable;
+use {Param, Result, ErrorKind, Token, NamedToken, ParamType, StateMutability, encode, decode, Bytes};
 
-/// Contract constructor specification.
+/// Constructor as written in json abi, with legacy payable flag.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
+struct ConstructorIr {
+	/// Constructor input.
+	inputs: Vec<Param>,
+	/// Legacy payable flag.
+	#[serde(default)]
+	payable: Option<bool>,
+	/// State mutability, emitted by solc >= 0.4.16.
+	#[serde(default, rename = "stateMutability")]
+	state_mutability: Option<StateMutability>,
+}
+
+/// Contract constructor specification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ConstructorIr")]
 pub struct Constructor {
 	/// Constructor input.
 	pub inputs: Vec<Param>,
+	/// Constructor state mutability, either `NonPayable` or `Payable`.
+	#[serde(rename = "stateMutability")]
+	pub state_mutability: StateMutability,
+}
+
+impl From<ConstructorIr> for Constructor {
+	fn from(c: ConstructorIr) -> Self {
+		Constructor {
+			inputs: c.inputs,
+			state_mutability: StateMutability::resolve(c.state_mutability, None, c.payable),
+		}
+	}
 }
 
 impl Constructor {
 	/// Returns all input params of given constructor.
 	fn param_types(&self) -> Vec<ParamType> {
 		self.inputs.iter()
-			.map(|p| p.kind.clone())
+			.map(|p| p.true_type())
 			.collect()
 	}
 
+	/// Returns human-readable constructor, eg. `constructor(string name) payable`.
+	pub fn human_readable(&self) -> String {
+		human_readable::format_constructor(self)
+	}
+
 	/// Prepares ABI constructor call with given input params.
 	pub fn encode_input(&self, code: Bytes, tokens: &[Token]) -> Result<Bytes> {
 		let params = self.param_types();
 
 		if Token::types_check(tokens, &params) {
-			Ok(code.into_iter().chain(encode(tokens)).collect())
+			let mut result = code;
+			result.reserve(ParamType::min_encoded_size(&params));
+			result.extend(encode(tokens));
+			Ok(result)
 		} else {
 			Err(ErrorKind::InvalidData.into())
 		}
 	}
+
+	/// Parses the ABI constructor call, ie. creation code of given length followed by
+	/// the encoded params, to list of named tokens.
+	pub fn decode_input(&self, data: &[u8], code_len: usize) -> Result<Vec<NamedToken>> {
+		if data.len() < code_len {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		let tokens = decode(&self.param_types(), &data[code_len..])?;
+		Ok(NamedToken::from_params(&self.inputs, tokens))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use {Constructor, NamedToken, StateMutability, Token};
+
+	#[test]
+	fn constructor_state_mutability() {
+		let constructor: Constructor = serde_json::from_str(r#"{ "inputs": [], "payable": true }"#).unwrap();
+		assert_eq!(constructor.state_mutability, StateMutability::Payable);
+
+		let constructor: Constructor = serde_json::from_str(r#"{ "inputs": [], "stateMutability": "nonpayable" }"#).unwrap();
+		assert_eq!(constructor.state_mutability, StateMutability::NonPayable);
+
+		let constructor: Constructor = serde_json::from_str(r#"{ "inputs": [] }"#).unwrap();
+		assert_eq!(constructor.state_mutability, StateMutability::NonPayable);
+	}
+
+	#[test]
+	fn constructor_decode_input() {
+		let constructor: Constructor = serde_json::from_str(r#"{
+			"inputs": [
+				{ "name": "owner", "type": "address" },
+				{ "name": "limits", "type": "uint16[]" }
+			]
+		}"#).unwrap();
+		let tokens = vec![
+			Token::Address([0x11u8; 20].into()),
+			Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+		];
+		let code = vec![0x60, 0x80, 0x60, 0x40];
+		let encoded = constructor.encode_input(code.clone(), &tokens).unwrap();
+
+		assert_eq!(constructor.decode_input(&encoded, code.len()).unwrap(), vec![
+			NamedToken { name: "owner".to_owned(), value: tokens[0].clone() },
+			NamedToken { name: "limits".to_owned(), value: tokens[1].clone() },
+		]);
+		assert!(constructor.decode_input(&encoded, encoded.len() + 1).is_err());
+		assert!(constructor.decode_input(&encoded, code.len() + 1).is_err());
+	}
 }
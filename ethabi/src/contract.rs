@@ -1,23 +1,43 @@
-use std::{io, fmt};
-use std::collections::HashMap;
-use std::collections::hash_map::Values;
-use serde::{Deserialize, Deserializer};
+use std::{io, fmt, slice};
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{Visitor, SeqAccess};
+use serde::ser::SerializeSeq;
 use serde_json;
+use hex::ToHex;
 use operation::Operation;
-use {errors, ErrorKind, Event, Constructor, Function};
+use human_readable;
+use merge;
+use natspec;
+use validate;
+use {errors, AbiError, Diagnostic, Diff, Documentation, Entry, ErrorKind, Event, Constructor, Fallback, Function, Hash, Log, MergePolicy, NamedToken, RawLog, Receive};
 
 /// API building calls to contracts ABI.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Entries are kept in declaration order and modified through `add_function`, `set_constructor`,
+/// `retain` and similar methods, which keep the lookup indexes up to date.
+#[derive(Clone, Debug, Default)]
 pub struct Contract {
-	/// Contract constructor.
-	pub constructor: Option<Constructor>,
-	/// Contract functions.
-	pub functions: HashMap<String, Function>,
-	/// Contract events.
-	pub events: HashMap<String, Event>,
-	/// Contract has fallback function.
-	pub fallback: bool,
+	// All abi entries in declaration order.
+	entries: Vec<Operation>,
+	// Positions of functions, events and errors in `entries` by name. Overloads share the same name.
+	functions: BTreeMap<String, Vec<usize>>,
+	events: BTreeMap<String, Vec<usize>>,
+	errors: BTreeMap<String, Vec<usize>>,
+	// Positions of functions by selector and of non-anonymous events by signature, ie. their first topic.
+	selectors: HashMap<[u8; 4], usize>,
+	topics: HashMap<Hash, usize>,
+	/// NatSpec documentation of entries, see `load_docs`.
+	///
+	/// Not part of the abi, so neither saved nor compared.
+	pub docs: BTreeMap<Entry, Documentation>,
+}
+
+impl PartialEq for Contract {
+	fn eq(&self, other: &Self) -> bool {
+		// indexes are derived from the entries, docs are not part of the abi
+		self.entries == other.entries
+	}
 }
 
 impl<'a> Deserialize<'a> for Contract {
@@ -36,89 +56,746 @@ impl<'a> Visitor<'a> for ContractVisitor {
 	}
 
 	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'a> {
-		let mut result = Contract {
-			constructor: None,
-			functions: HashMap::default(),
-			events: HashMap::default(),
-			fallback: false,
-		};
+		let mut result = Contract::default();
 
 		while let Some(operation) = seq.next_element()? {
-			match operation {
-				Operation::Constructor(constructor) => {
-					result.constructor = Some(constructor);
-				},
-				Operation::Function(func) => {
-					result.functions.insert(func.name.clone(), func);
-				},
-				Operation::Event(event) => {
-					result.events.insert(event.name.clone(), event);
-				},
-				Operation::Fallback => {
-					result.fallback = true;
-				},
-			}
+			result.insert(operation);
 		}
 
 		Ok(result)
 	}
 }
 
+impl Serialize for Contract {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		let mut seq = serializer.serialize_seq(Some(self.entries.len()))?;
+		for operation in &self.entries {
+			seq.serialize_element(operation)?;
+		}
+		seq.end()
+	}
+}
+
 impl Contract {
 	/// Loads contract from json.
 	pub fn load<T: io::Read>(reader: T) -> errors::Result<Self> {
 		serde_json::from_reader(reader).map_err(From::from)
 	}
 
+	/// Loads contract from human-readable abi, eg.
+	/// `["function balanceOf(address owner) view returns (uint256)"]`.
+	///
+	/// Empty lines are ignored.
+	pub fn from_human_readable(lines: &[&str]) -> errors::Result<Self> {
+		let mut result = Contract::default();
+
+		for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+			result.insert(human_readable::parse_operation(line)?);
+		}
+
+		Ok(result)
+	}
+
+	/// Loads NatSpec documentation of contract entries from solc `userdoc` and `devdoc` output.
+	pub fn load_docs<T: io::Read, U: io::Read>(&mut self, userdoc: T, devdoc: U) -> errors::Result<()> {
+		self.docs = natspec::parse(serde_json::from_reader(userdoc)?, serde_json::from_reader(devdoc)?)?;
+		Ok(())
+	}
+
+	/// Returns NatSpec documentation of the entry.
+	pub fn docs(&self, entry: &Entry) -> Option<&Documentation> {
+		self.docs.get(entry)
+	}
+
+	/// Returns contract as human-readable abi, one entry per line.
+	pub fn to_human_readable(&self) -> Vec<String> {
+		self.entries.iter().map(human_readable::format_operation).collect()
+	}
+
+	/// Iterate over all abi entries in declaration order.
+	pub(crate) fn operations<'a>(&'a self) -> slice::Iter<'a, Operation> {
+		self.entries.iter()
+	}
+
+	/// Adds the entry after all other entries.
+	///
+	/// Constructor, fallback and receive functions replace the current one in place.
+	pub(crate) fn insert(&mut self, operation: Operation) {
+		let position = self.entries.len();
+		match operation {
+			Operation::Constructor(_) | Operation::Fallback(_) | Operation::Receive(_) => {
+				let entry = operation.entry();
+				match self.entries.iter().position(|o| o.entry() == entry) {
+					Some(replaced) => self.entries[replaced] = operation,
+					None => self.entries.push(operation),
+				}
+				return;
+			},
+			Operation::Function(ref function) => {
+				self.functions.entry(function.name.clone()).or_default().push(position);
+				self.selectors.entry(function.selector()).or_insert(position);
+			},
+			Operation::Event(ref event) => {
+				self.events.entry(event.name.clone()).or_default().push(position);
+				if !event.anonymous {
+					self.topics.entry(event.signature()).or_insert(position);
+				}
+			},
+			Operation::Error(ref error) => {
+				self.errors.entry(error.name.clone()).or_default().push(position);
+			},
+		}
+		self.entries.push(operation);
+	}
+
+	/// Sets contract constructor, replacing the current one in place.
+	pub fn set_constructor(&mut self, constructor: Constructor) {
+		self.insert(Operation::Constructor(constructor));
+	}
+
+	/// Adds function after all other entries, keeping other overloads.
+	pub fn add_function(&mut self, function: Function) {
+		self.insert(Operation::Function(function));
+	}
+
+	/// Adds event after all other entries, keeping other overloads.
+	pub fn add_event(&mut self, event: Event) {
+		self.insert(Operation::Event(event));
+	}
+
+	/// Adds custom error after all other entries, keeping other overloads.
+	pub fn add_error(&mut self, error: AbiError) {
+		self.insert(Operation::Error(error));
+	}
+
+	/// Sets contract fallback function, replacing the current one in place.
+	pub fn set_fallback(&mut self, fallback: Fallback) {
+		self.insert(Operation::Fallback(fallback));
+	}
+
+	/// Sets contract receive function, replacing the current one in place.
+	pub fn set_receive(&mut self, receive: Receive) {
+		self.insert(Operation::Receive(receive));
+	}
+
+	/// Retains only the entries for which the predicate returns true, eg.
+	/// `contract.retain(|entry| *entry != Entry::Constructor)`.
+	///
+	/// Documentation of removed entries is dropped as well.
+	pub fn retain<F>(&mut self, mut keep: F) where F: FnMut(&Entry) -> bool {
+		let entries = ::std::mem::take(&mut self.entries);
+		self.functions.clear();
+		self.events.clear();
+		self.errors.clear();
+		self.selectors.clear();
+		self.topics.clear();
+		for operation in entries {
+			if keep(&operation.entry()) {
+				self.insert(operation);
+			}
+		}
+
+		let positions = self.entries.iter().map(Operation::entry).collect::<Vec<_>>();
+		self.docs.retain(|entry, _| positions.contains(entry));
+	}
+
+	/// Merges contracts into one, eg. a proxy with its implementation.
+	///
+	/// Identical entries are merged into one and overloads are kept. Differing entries with
+	/// the same selector or event signature are resolved according to the policy.
+	pub fn merge<'a, I>(contracts: I, policy: MergePolicy) -> errors::Result<Contract> where I: IntoIterator<Item = &'a Contract> {
+		merge::merge(contracts, policy)
+	}
+
+	/// Returns problems that make the abi unusable, eg. invalid types or duplicate selectors.
+	pub fn validate(&self) -> Vec<Diagnostic> {
+		validate::validate(self)
+	}
+
+	/// Returns interface changes between this and the new version of the contract.
+	pub fn diff(&self, new: &Contract) -> Diff {
+		Diff::new(self, new)
+	}
+
+	/// Returns position of the entry among all entries of the contract, ie. in the abi
+	/// the contract was loaded from.
+	pub fn position(&self, entry: &Entry) -> Option<usize> {
+		let by_name = |index: &BTreeMap<String, Vec<usize>>, signature: &str| {
+			let name = signature.split('(').next().unwrap_or(signature);
+			index.get(name)
+				.and_then(|positions| positions.iter().find(|&&position| self.entries[position].entry() == *entry))
+				.cloned()
+		};
+
+		match *entry {
+			Entry::Function(ref signature) => by_name(&self.functions, signature),
+			Entry::Event(ref signature) => by_name(&self.events, signature),
+			Entry::Error(ref signature) => by_name(&self.errors, signature),
+			Entry::Constructor | Entry::Fallback | Entry::Receive => self.entries.iter().position(|o| o.entry() == *entry),
+		}
+	}
+
+	/// Writes contract as json abi.
+	pub fn save<T: io::Write>(&self, writer: T) -> errors::Result<()> {
+		serde_json::to_writer_pretty(writer, self).map_err(From::from)
+	}
+
 	/// Creates constructor call builder.
 	pub fn constructor(&self) -> Option<&Constructor> {
-		self.constructor.as_ref()
+		self.entries.iter()
+			.filter_map(|operation| match *operation {
+				Operation::Constructor(ref constructor) => Some(constructor),
+				_ => None,
+			})
+			.next()
 	}
 
 	/// Creates function call builder.
+	///
+	/// Fails if the function is overloaded, use `function_by_signature`
+	/// or `function_by_selector` instead.
 	pub fn function(&self, name: &str) -> errors::Result<&Function> {
-		self.functions.get(name).ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
+		let mut overloads = self.overloads(name);
+		match (overloads.next(), overloads.next()) {
+			(Some(function), None) => Ok(function),
+			(None, _) => Err(ErrorKind::InvalidName(name.to_owned()).into()),
+			(Some(_), Some(_)) => Err(ErrorKind::AmbiguousName(name.to_owned()).into()),
+		}
+	}
+
+	/// Returns function with given canonical signature, eg. `transfer(address,uint256)`.
+	pub fn function_by_signature(&self, signature: &str) -> errors::Result<&Function> {
+		let name = signature.split('(').next().unwrap_or(signature);
+		self.overloads(name)
+			.find(|f| f.signature() == signature)
+			.ok_or_else(|| ErrorKind::InvalidName(signature.to_owned()).into())
+	}
+
+	/// Returns function with given 4-byte selector.
+	pub fn function_by_selector(&self, selector: [u8; 4]) -> errors::Result<&Function> {
+		self.selectors.get(&selector)
+			.and_then(|&position| match self.entries[position] {
+				Operation::Function(ref function) => Some(function),
+				_ => None,
+			})
+			.ok_or_else(|| ErrorKind::InvalidName(selector.to_hex()).into())
+	}
+
+	/// Decodes function call, finding the function by the selector of given calldata.
+	pub fn decode_call(&self, data: &[u8]) -> errors::Result<(&Function, Vec<NamedToken>)> {
+		if data.len() < 4 {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&data[..4]);
+		let function = self.function_by_selector(selector)?;
+		let tokens = function.decode_input(data)?;
+		Ok((function, tokens))
+	}
+
+	/// Iterate over all overloads of the function with given name.
+	pub fn overloads(&self, name: &str) -> Overloads {
+		Overloads {
+			entries: &self.entries,
+			positions: self.functions.get(name).map(|p| p.iter()).unwrap_or_else(|| [].iter()),
+		}
 	}
 
 	/// Creates event decoder.
+	///
+	/// Fails if the event is overloaded, use `event_by_signature` or `event_by_topic` instead.
 	pub fn event(&self, name: &str) -> errors::Result<&Event> {
-		self.events.get(name).ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
+		let mut overloads = self.event_overloads(name);
+		match (overloads.next(), overloads.next()) {
+			(Some(event), None) => Ok(event),
+			(None, _) => Err(ErrorKind::InvalidName(name.to_owned()).into()),
+			(Some(_), Some(_)) => Err(ErrorKind::AmbiguousName(name.to_owned()).into()),
+		}
+	}
+
+	/// Returns event with given canonical signature, eg. `Transfer(address,address,uint256)`.
+	pub fn event_by_signature(&self, signature: &str) -> errors::Result<&Event> {
+		let name = signature.split('(').next().unwrap_or(signature);
+		self.event_overloads(name)
+			.find(|e| e.signature_string() == signature)
+			.ok_or_else(|| ErrorKind::InvalidName(signature.to_owned()).into())
+	}
+
+	/// Returns non-anonymous event with given signature, ie. the first topic of its logs.
+	pub fn event_by_topic(&self, topic: &Hash) -> errors::Result<&Event> {
+		self.topics.get(topic)
+			.and_then(|&position| match self.entries[position] {
+				Operation::Event(ref event) => Some(event),
+				_ => None,
+			})
+			.ok_or_else(|| ErrorKind::InvalidName(format!("{:x}", topic)).into())
+	}
+
+	/// Iterate over all overloads of the event with given name.
+	pub fn event_overloads<'a>(&'a self, name: &str) -> EventOverloads<'a> {
+		EventOverloads {
+			entries: &self.entries,
+			positions: self.events.get(name).map(|p| p.iter()).unwrap_or_else(|| [].iter()),
+		}
+	}
+
+	/// Decodes log of a non-anonymous event, finding the event by the first topic.
+	pub fn decode_log(&self, log: RawLog) -> errors::Result<(&Event, Log)> {
+		let event = {
+			let topic = log.topics.first().ok_or(ErrorKind::InvalidData)?;
+			self.event_by_topic(topic)?
+		};
+		let decoded = event.parse_log(log)?;
+		Ok((event, decoded))
+	}
+
+	/// Returns custom error with given name.
+	///
+	/// Fails if the error is overloaded.
+	pub fn error(&self, name: &str) -> errors::Result<&AbiError> {
+		let positions = self.errors.get(name).ok_or_else(|| ErrorKind::InvalidName(name.to_owned()))?;
+		match (positions.len(), &self.entries[positions[0]]) {
+			(1, &Operation::Error(ref error)) => Ok(error),
+			_ => Err(ErrorKind::AmbiguousName(name.to_owned()).into()),
+		}
 	}
 
-	/// Iterate over all functions of the contract in arbitrary order.
+	/// Decodes revert data using the custom error matching its selector.
+	pub fn decode_revert(&self, data: &[u8]) -> errors::Result<(&AbiError, Vec<NamedToken>)> {
+		if data.len() < 4 {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		let error = self.errors()
+			.find(|e| e.selector() == data[..4])
+			.ok_or_else(|| ErrorKind::InvalidName(data[..4].to_hex()))?;
+		let tokens = error.decode_named(data)?;
+		Ok((error, tokens))
+	}
+
+	/// Iterate over all functions of the contract in declaration order.
 	pub fn functions(&self) -> Functions {
-		Functions(self.functions.values())
+		Functions(self.entries.iter())
 	}
 
-	/// Iterate over all events of the contract in arbitrary order.
+	/// Iterate over all events of the contract in declaration order.
 	pub fn events(&self) -> Events {
-		Events(self.events.values())
+		Events(self.entries.iter())
+	}
+
+	/// Iterate over all custom errors of the contract in declaration order.
+	pub fn errors(&self) -> AbiErrors {
+		AbiErrors(self.entries.iter())
+	}
+
+	/// Returns contract fallback function.
+	pub fn fallback(&self) -> Option<&Fallback> {
+		self.entries.iter()
+			.filter_map(|operation| match *operation {
+				Operation::Fallback(ref fallback) => Some(fallback),
+				_ => None,
+			})
+			.next()
+	}
+
+	/// Returns contract receive function.
+	pub fn receive(&self) -> Option<&Receive> {
+		self.entries.iter()
+			.filter_map(|operation| match *operation {
+				Operation::Receive(ref receive) => Some(receive),
+				_ => None,
+			})
+			.next()
 	}
 
-	/// Returns true if contract has fallback
-	pub fn fallback(&self) -> bool {
-		self.fallback
+	/// Returns true if a call with empty calldata and non-zero value, eg. a plain ether
+	/// transfer, is accepted by the contract.
+	pub fn accepts_plain_transfer(&self) -> bool {
+		self.receive().is_some() || self.fallback().map_or(false, |f| f.state_mutability.is_payable())
+	}
+
+	/// Returns true if a call with a selector not matching any function is accepted
+	/// by the contract.
+	pub fn accepts_unknown_selector(&self, with_value: bool) -> bool {
+		self.fallback().map_or(false, |f| !with_value || f.state_mutability.is_payable())
 	}
 }
 
 /// Contract functions interator.
-pub struct Functions<'a>(Values<'a, String, Function>);
+pub struct Functions<'a>(slice::Iter<'a, Operation>);
 
 impl<'a> Iterator for Functions<'a> {
 	type Item = &'a Function;
 
 	fn next(&mut self) -> Option<Self::Item> {
-		self.0.next()
+		while let Some(operation) = self.0.next() {
+			if let Operation::Function(ref function) = *operation {
+				return Some(function);
+			}
+		}
+		None
 	}
 }
 
 /// Contract events interator.
-pub struct Events<'a>(Values<'a, String, Event>);
+pub struct Events<'a>(slice::Iter<'a, Operation>);
 
 impl<'a> Iterator for Events<'a> {
 	type Item = &'a Event;
 
 	fn next(&mut self) -> Option<Self::Item> {
-		self.0.next()
+		while let Some(operation) = self.0.next() {
+			if let Operation::Event(ref event) = *operation {
+				return Some(event);
+			}
+		}
+		None
+	}
+}
+
+/// Contract custom errors interator.
+pub struct AbiErrors<'a>(slice::Iter<'a, Operation>);
+
+impl<'a> Iterator for AbiErrors<'a> {
+	type Item = &'a AbiError;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while let Some(operation) = self.0.next() {
+			if let Operation::Error(ref error) = *operation {
+				return Some(error);
+			}
+		}
+		None
+	}
+}
+
+/// Function overloads iterator.
+pub struct Overloads<'a> {
+	entries: &'a [Operation],
+	positions: slice::Iter<'a, usize>,
+}
+
+impl<'a> Iterator for Overloads<'a> {
+	type Item = &'a Function;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let entries = self.entries;
+		self.positions.next().and_then(|&position| match entries[position] {
+			Operation::Function(ref function) => Some(function),
+			_ => None,
+		})
+	}
+}
+
+/// Event overloads iterator.
+pub struct EventOverloads<'a> {
+	entries: &'a [Operation],
+	positions: slice::Iter<'a, usize>,
+}
+
+impl<'a> Iterator for EventOverloads<'a> {
+	type Item = &'a Event;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let entries = self.entries;
+		self.positions.next().and_then(|&position| match entries[position] {
+			Operation::Event(ref event) => Some(event),
+			_ => None,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs::{self, File};
+	use {Artifact, Contract, Entry, ErrorKind, NamedToken, RawLog, StateMutability, Token};
+
+	const OVERLOADED: &str = r#"[
+		{
+			"type": "function",
+			"name": "transfer",
+			"inputs": [
+				{ "name": "to", "type": "address" },
+				{ "name": "value", "type": "uint256" }
+			],
+			"outputs": []
+		},
+		{
+			"type": "function",
+			"name": "transfer",
+			"inputs": [
+				{ "name": "to", "type": "address" },
+				{ "name": "value", "type": "uint256" },
+				{ "name": "data", "type": "bytes" }
+			],
+			"outputs": []
+		},
+		{
+			"type": "function",
+			"name": "balanceOf",
+			"inputs": [
+				{ "name": "owner", "type": "address" }
+			],
+			"outputs": [
+				{ "name": "", "type": "uint256" }
+			]
+		}
+	]"#;
+
+	#[test]
+	fn keeps_overloaded_functions() {
+		let contract = Contract::load(OVERLOADED.as_bytes()).unwrap();
+
+		assert_eq!(contract.functions().count(), 3);
+		assert_eq!(contract.overloads("transfer").count(), 2);
+		assert_eq!(contract.overloads("approve").count(), 0);
+		assert_eq!(contract.function("balanceOf").unwrap().signature(), "balanceOf(address)");
+
+		match *contract.function("transfer").unwrap_err().kind() {
+			ErrorKind::AmbiguousName(ref name) => assert_eq!(name, "transfer"),
+			ref other => panic!("unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn keeps_declaration_order() {
+		let mut contract = Contract::load(OVERLOADED.as_bytes()).unwrap();
+		let signatures = |contract: &Contract| contract.functions().map(|f| f.signature()).collect::<Vec<_>>();
+
+		assert_eq!(signatures(&contract), vec!["transfer(address,uint256)", "transfer(address,uint256,bytes)", "balanceOf(address)"]);
+		assert_eq!(contract.position(&Entry::Function("balanceOf(address)".to_owned())), Some(2));
+		assert_eq!(contract.to_human_readable()[0], "function transfer(address to, uint256 value)");
+
+		let approve = Contract::from_human_readable(&["function approve(address spender, uint256 value)"]).unwrap();
+		contract.retain(|entry| *entry == Entry::Function("balanceOf(address)".to_owned()));
+		contract.add_function(approve.function("approve").unwrap().clone());
+
+		assert_eq!(signatures(&contract), vec!["balanceOf(address)", "approve(address,uint256)"]);
+		assert_eq!(contract.position(&Entry::Function("approve(address,uint256)".to_owned())), Some(1));
+		assert_eq!(contract.position(&Entry::Function("transfer(address,uint256)".to_owned())), None);
+	}
+
+	#[test]
+	fn function_by_signature_and_selector() {
+		let contract = Contract::load(OVERLOADED.as_bytes()).unwrap();
+
+		let transfer = contract.function_by_signature("transfer(address,uint256,bytes)").unwrap();
+		assert_eq!(transfer.inputs.len(), 3);
+		assert!(contract.function_by_signature("transfer(address)").is_err());
+
+		let transfer = contract.function_by_selector(hex!("a9059cbb")).unwrap();
+		assert_eq!(transfer.signature(), "transfer(address,uint256)");
+		assert!(contract.function_by_selector([0u8; 4]).is_err());
+	}
+
+	#[test]
+	fn decode_call_and_log() {
+		let contract = Contract::from_human_readable(&[
+			"function transfer(address to, uint256 value) returns (bool)",
+			"function transfer(address to, uint256 value, bytes data) returns (bool)",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"event Anonymous(uint256 value) anonymous",
+		]).unwrap();
+
+		let to = Token::Address([0x11u8; 20].into());
+		let data = contract.function_by_signature("transfer(address,uint256,bytes)").unwrap()
+			.encode_input(&[to.clone(), Token::Uint(5.into()), Token::Bytes(vec![1])])
+			.unwrap();
+		let (function, tokens) = contract.decode_call(&data).unwrap();
+		assert_eq!(function.inputs.len(), 3);
+		assert_eq!(tokens[0], NamedToken { name: "to".to_owned(), value: to.clone() });
+		assert!(contract.decode_call(&data[..3]).is_err());
+		assert!(contract.decode_call(&[0u8; 4]).is_err());
+
+		let transfer = contract.event("Transfer").unwrap();
+		let log = RawLog {
+			topics: vec![
+				transfer.signature(),
+				"0000000000000000000000002222222222222222222222222222222222222222".parse().unwrap(),
+				"0000000000000000000000001111111111111111111111111111111111111111".parse().unwrap(),
+			],
+			data: hex!("0000000000000000000000000000000000000000000000000000000000000007").to_vec(),
+		};
+		let (event, decoded) = contract.decode_log(log.clone()).unwrap();
+		assert_eq!(event.name, "Transfer");
+		assert_eq!(decoded.params[1].value, to);
+		assert!(contract.decode_log(RawLog { topics: vec![], data: vec![] }).is_err());
+
+		let mut modified = contract.clone();
+		modified.retain(|entry| match *entry {
+			Entry::Function(_) => false,
+			_ => true,
+		});
+		assert!(modified.decode_call(&data).is_err());
+		assert_eq!(modified.decode_log(log).unwrap().0.name, "Transfer");
+	}
+
+	#[test]
+	fn lookups_follow_modification() {
+		let mut contract = Contract::from_human_readable(&[
+			"function transfer(address to, uint256 value) returns (bool)",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"constructor(string name)",
+		]).unwrap();
+
+		let mut transfer = contract.function("transfer").unwrap().clone();
+		transfer.inputs.pop();
+		let mut event = contract.event("Transfer").unwrap().clone();
+		let old_topic = event.signature();
+		event.inputs.pop();
+		contract.retain(|entry| *entry == Entry::Constructor);
+		contract.add_function(transfer);
+		contract.add_event(event);
+
+		let transfer = contract.function_by_selector(contract.function("transfer").unwrap().selector()).unwrap();
+		assert_eq!(transfer.signature(), "transfer(address)");
+		assert!(contract.function_by_selector(hex!("a9059cbb")).is_err());
+
+		let topic = contract.event("Transfer").unwrap().signature();
+		assert_eq!(contract.event_by_topic(&topic).unwrap().inputs.len(), 2);
+		assert!(contract.event_by_topic(&old_topic).is_err());
+		assert_eq!(contract.position(&Entry::Event("Transfer(address,address)".to_owned())), Some(2));
+
+		let constructor = Contract::from_human_readable(&["constructor()"]).unwrap().constructor().unwrap().clone();
+		contract.set_constructor(constructor);
+		assert!(contract.constructor().unwrap().inputs.is_empty());
+		assert_eq!(contract.position(&Entry::Constructor), Some(0));
+	}
+
+	#[test]
+	fn keeps_overloaded_events() {
+		let contract = Contract::from_human_readable(&[
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"event Transfer(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)",
+		]).unwrap();
+
+		assert_eq!(contract.events().count(), 2);
+		assert_eq!(contract.event_overloads("Transfer").count(), 2);
+		match *contract.event("Transfer").unwrap_err().kind() {
+			ErrorKind::AmbiguousName(ref name) => assert_eq!(name, "Transfer"),
+			ref other => panic!("unexpected error: {:?}", other),
+		}
+
+		let single = contract.event_by_signature("Transfer(address,address,address,uint256,uint256)").unwrap();
+		assert_eq!(single.inputs.len(), 5);
+		assert_eq!(contract.event_by_topic(&single.signature()).unwrap(), single);
+		assert_eq!(contract.position(&Entry::Event(single.signature_string())), Some(1));
+	}
+
+	#[test]
+	fn decode_revert_with_custom_error() {
+		let s = r#"[
+			{
+				"type": "error",
+				"name": "InsufficientBalance",
+				"inputs": [
+					{ "name": "available", "type": "uint256" },
+					{ "name": "required", "type": "uint256" }
+				]
+			},
+			{
+				"type": "error",
+				"name": "Unauthorized",
+				"inputs": []
+			}
+		]"#;
+
+		let contract = Contract::load(s.as_bytes()).unwrap();
+		assert_eq!(contract.errors().count(), 2);
+
+		let data = hex!("
+			cf479181
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000002
+		");
+		let (error, tokens) = contract.decode_revert(&data).unwrap();
+		assert_eq!(error.name, "InsufficientBalance");
+		assert_eq!(tokens, vec![
+			NamedToken { name: "available".to_owned(), value: Token::Uint(1.into()) },
+			NamedToken { name: "required".to_owned(), value: Token::Uint(2.into()) },
+		]);
+
+		let unauthorized = contract.error("Unauthorized").unwrap();
+		let (error, tokens) = contract.decode_revert(&unauthorized.selector()).unwrap();
+		assert_eq!(error.name, "Unauthorized");
+		assert!(tokens.is_empty());
+
+		assert!(contract.decode_revert(&hex!("08c379a0")).is_err());
+	}
+
+	#[test]
+	fn receive_and_fallback() {
+		let s = r#"[
+			{ "type": "receive", "stateMutability": "payable" },
+			{ "type": "fallback", "stateMutability": "nonpayable" }
+		]"#;
+
+		let contract = Contract::load(s.as_bytes()).unwrap();
+		assert_eq!(contract.receive().unwrap().state_mutability, StateMutability::Payable);
+		assert_eq!(contract.fallback().unwrap().state_mutability, StateMutability::NonPayable);
+		assert!(contract.accepts_plain_transfer());
+		assert!(contract.accepts_unknown_selector(false));
+		assert!(!contract.accepts_unknown_selector(true));
+
+		let s = r#"[{ "type": "fallback", "payable": true }]"#;
+		let contract = Contract::load(s.as_bytes()).unwrap();
+		assert!(contract.receive().is_none());
+		assert!(contract.accepts_plain_transfer());
+		assert!(contract.accepts_unknown_selector(true));
+
+		let contract = Contract::load("[]".as_bytes()).unwrap();
+		assert!(!contract.accepts_plain_transfer());
+		assert!(!contract.accepts_unknown_selector(false));
+	}
+
+	#[test]
+	fn roundtrip_res_abis() {
+		let entries = fs::read_dir("../res").unwrap().chain(fs::read_dir("../res/diamond").unwrap());
+		let mut count = 0;
+		for entry in entries {
+			let path = entry.unwrap().path();
+			let contracts = match path.extension().and_then(|ext| ext.to_str()) {
+				Some("abi") => vec![Contract::load(File::open(&path).unwrap()).unwrap()],
+				Some("json") => Artifact::load_all(File::open(&path).unwrap()).unwrap()
+					.into_iter()
+					.map(|artifact| artifact.contract)
+					.collect(),
+				_ => continue,
+			};
+
+			for contract in contracts {
+				let mut json = vec![];
+				contract.save(&mut json).unwrap();
+				assert_eq!(Contract::load(&json[..]).unwrap(), contract, "{} does not roundtrip", path.display());
+				count += 1;
+			}
+		}
+		assert!(count > 15, "expected all abis in res, found {}", count);
+	}
+
+	#[test]
+	fn roundtrip_tuple_params() {
+		let contract = Contract::from_human_readable(&[
+			"function fill(tuple(address maker, uint256[2] amounts) order, (bool, (string name)[])[] extras) returns ((uint256 id))",
+			"event Filled(tuple(address maker, bytes data) indexed order, (uint8, bool) flags)",
+			"error Rejected((address, uint256) order)",
+		]).unwrap();
+		let mut json = vec![];
+		contract.save(&mut json).unwrap();
+		assert_eq!(Contract::load(&json[..]).unwrap(), contract);
+
+		// params created without components are written with unnamed components
+		let mut fill = contract.function("fill").unwrap().clone();
+		for param in fill.inputs.iter_mut() {
+			param.components.clear();
+		}
+		let mut contract = Contract::default();
+		contract.add_function(fill);
+		let mut json = vec![];
+		contract.save(&mut json).unwrap();
+		assert!(!String::from_utf8(json.clone()).unwrap().contains(r#""type": "("#));
+		let loaded = Contract::load(&json[..]).unwrap();
+		assert_eq!(loaded.function("fill").unwrap().signature(), "fill((address,uint256[2]),(bool,(string)[])[])");
 	}
 }
@@ -1,7 +1,7 @@
 //! ABI decoder.
 
-use util::slice_data;
-use {Token, ErrorKind, Error, ResultExt, ParamType};
+use util::{fits_uint, slice_data};
+use {Token, ErrorKind, Error, I256, ResultExt, ParamType};
 
 struct DecodeResult {
 	token: Token,
@@ -41,6 +41,9 @@ pub fn decode(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Error> {
     if !is_empty_bytes_valid_encoding && data.is_empty() {
         bail!("please ensure the contract and method you're calling exist! failed to decode empty bytes. if you're using jsonrpc this is likely due to jsonrpc returning `0x` in case contract or method don't exist");
     }
+	if data.len() < ParamType::min_encoded_size(types) {
+		return Err(ErrorKind::InvalidData.into());
+	}
 	let slices = slice_data(data)?;
 	let mut tokens = vec![];
 	let mut offset = 0;
@@ -93,11 +96,14 @@ fn decode_param(param: &ParamType, slices: &[[u8; 32]], offset: usize) -> Result
 
 			Ok(result)
 		},
-		ParamType::Int(_) => {
+		ParamType::Int(size) => {
 			let slice = try!(peek(slices, offset));
+			if !I256::from_raw((*slice).into()).fits(size) {
+				return Err(ErrorKind::InvalidData.into());
+			}
 
 			let result = DecodeResult {
-				token: Token::Int(slice.clone().into()),
+				token: Token::Int((*slice).into()),
 				new_offset: offset + 1,
 				tail_consumed: 0,
 			};
@@ -108,7 +114,53 @@ fn decode_param(param: &ParamType, slices: &[[u8; 32]], offset: usize) -> Result
 			let slice = try!(peek(slices, offset));
 
 			let result = DecodeResult {
-				token: Token::Uint(slice.clone().into()),
+				token: Token::Uint((*slice).into()),
+				new_offset: offset + 1,
+				tail_consumed: 0,
+			};
+
+			Ok(result)
+		},
+		ParamType::Fixed(size, _) => {
+			let slice = peek(slices, offset)?;
+			if !I256::from_raw((*slice).into()).fits(size) {
+				return Err(ErrorKind::InvalidData.into());
+			}
+
+			let result = DecodeResult {
+				token: Token::Fixed((*slice).into()),
+				new_offset: offset + 1,
+				tail_consumed: 0,
+			};
+
+			Ok(result)
+		},
+		ParamType::UFixed(size, _) => {
+			let slice = peek(slices, offset)?;
+			if !fits_uint((*slice).into(), size) {
+				return Err(ErrorKind::InvalidData.into());
+			}
+
+			let result = DecodeResult {
+				token: Token::UFixed((*slice).into()),
+				new_offset: offset + 1,
+				tail_consumed: 0,
+			};
+
+			Ok(result)
+		},
+		ParamType::Function => {
+			let slice = peek(slices, offset)?;
+			if !slice[24..].iter().all(|x| *x == 0) {
+				return Err(ErrorKind::InvalidData.into());
+			}
+			let mut address = [0u8; 20];
+			address.copy_from_slice(&slice[..20]);
+			let mut selector = [0u8; 4];
+			selector.copy_from_slice(&slice[20..24]);
+
+			let result = DecodeResult {
+				token: Token::Function(address.into(), selector),
 				new_offset: offset + 1,
 				tail_consumed: 0,
 			};
@@ -181,6 +233,10 @@ fn decode_param(param: &ParamType, slices: &[[u8; 32]], offset: usize) -> Result
 			let len = try!(as_u32(len_slice)) as usize;
 
 			let sub_slices = &slices[len_offset + 1..];
+			// reject lengths exceeding the data before allocating tokens
+			if len * t.head_size() > sub_slices.len() * 32 {
+				return Err(ErrorKind::InvalidData.into());
+			}
 			let mut tokens = vec![];
 			let mut new_offset = 0;
 
@@ -262,7 +318,7 @@ fn decode_param(param: &ParamType, slices: &[[u8; 32]], offset: usize) -> Result
 
 #[cfg(test)]
 mod tests {
-	use {decode, Token, ParamType};
+	use {decode, I256, Token, ParamType};
 
 	#[test]
 	fn decode_address() {
@@ -310,11 +366,60 @@ mod tests {
 
 	#[test]
 	fn decode_int() {
+		// not sign-extended from 32 bits, used to be decoded as is
+		let encoded = hex!("1111111111111111111111111111111111111111111111111111111111111111");
+		assert!(decode(&[ParamType::Int(32)], &encoded).is_err());
+	}
+
+	#[test]
+	fn decode_int256() {
 		let encoded = hex!("1111111111111111111111111111111111111111111111111111111111111111");
 		let int = Token::Int([0x11u8; 32].into());
 		let expected = vec![int];
-		let decoded = decode(&[ParamType::Int(32)], &encoded).unwrap();
+		let decoded = decode(&[ParamType::Int(256)], &encoded).unwrap();
+		assert_eq!(decoded, expected);
+	}
+
+	#[test]
+	fn decode_narrow_int() {
+		let encoded = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe");
+		let decoded = decode(&[ParamType::Int(8)], &encoded).unwrap();
+		assert_eq!(decoded, vec![Token::Int(I256::from(-2i8).into())]);
+
+		// not sign-extended
+		let encoded = hex!("00000000000000000000000000000000000000000000000000000000000000fe");
+		assert!(decode(&[ParamType::Int(8)], &encoded).is_err());
+	}
+
+	#[test]
+	fn decode_fixed() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000096
+			ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff
+		");
+		let expected = vec![Token::UFixed(150.into()), Token::Fixed([0xffu8; 32].into())];
+		let decoded = decode(&[ParamType::UFixed(128, 2), ParamType::Fixed(8, 1)], &encoded).unwrap();
 		assert_eq!(decoded, expected);
+
+		// not sign-extended
+		let encoded = hex!("00000000000000000000000000000000000000000000000000000000000000ff");
+		assert!(decode(&[ParamType::Fixed(8, 1)], &encoded).is_err());
+
+		// exceeds size
+		let encoded = hex!("0000000000000000000000000000000000000000000000000000000000000100");
+		assert!(decode(&[ParamType::UFixed(8, 1)], &encoded).is_err());
+	}
+
+	#[test]
+	fn decode_function() {
+		let encoded = hex!("1111111111111111111111111111111111111111a9059cbb0000000000000000");
+		let expected = vec![Token::Function([0x11u8; 20].into(), [0xa9, 0x05, 0x9c, 0xbb])];
+		let decoded = decode(&[ParamType::Function], &encoded).unwrap();
+		assert_eq!(decoded, expected);
+
+		// non-zero padding
+		let encoded = hex!("1111111111111111111111111111111111111111a9059cbb0000000000000001");
+		assert!(decode(&[ParamType::Function], &encoded).is_err());
 	}
 
 	#[test]
@@ -564,6 +669,21 @@ mod tests {
 		assert!(decode(&[ParamType::FixedArray(Box::new(ParamType::Bool), 0)], &[]).is_ok());
 	}
 
+	#[test]
+	fn decode_too_short_data() {
+		// offset of the array, but no length
+		let encoded = hex!("0000000000000000000000000000000000000000000000000000000000000020");
+		assert!(decode(&[ParamType::Array(Box::new(ParamType::Address))], &encoded).is_err());
+
+		// array length exceeding the data
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			00000000000000000000000000000000000000000000000000000000ffffffff
+			0000000000000000000000001111111111111111111111111111111111111111
+		");
+		assert!(decode(&[ParamType::Array(Box::new(ParamType::Address))], &encoded).is_err());
+	}
+
 	#[test]
 	fn decode_tuple() {
 		let encoded = hex!("
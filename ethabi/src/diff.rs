@@ -0,0 +1,422 @@
+//! Interface changes between two versions of a contract.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use param_type::Writer;
+use {AbiError, Contract, Entry, Event, Function, Param, ParamType, StateMutability};
+
+/// Whether a renamed param is an input or an output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamLocation {
+	/// Function, event or error input.
+	Input,
+	/// Function output.
+	Output,
+}
+
+/// Single interface change.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+	/// Entry is present only in the new version.
+	Added(Entry),
+	/// Entry is present only in the old version.
+	Removed(Entry),
+	/// Entry takes different types, eg. an overload with the same name and number of inputs
+	/// was replaced.
+	InputsChanged {
+		/// Changed entry, identified by its old signature.
+		entry: Entry,
+		/// Old input types.
+		old: Vec<ParamType>,
+		/// New input types.
+		new: Vec<ParamType>,
+	},
+	/// Function returns different types.
+	OutputsChanged {
+		/// Changed function.
+		entry: Entry,
+		/// Old output types.
+		old: Vec<ParamType>,
+		/// New output types.
+		new: Vec<ParamType>,
+	},
+	/// Function state mutability changed.
+	StateMutabilityChanged {
+		/// Changed function.
+		entry: Entry,
+		/// Old state mutability.
+		old: StateMutability,
+		/// New state mutability.
+		new: StateMutability,
+	},
+	/// Event params are indexed differently, or the event became (non-)anonymous.
+	EventLayoutChanged {
+		/// Changed event.
+		entry: Entry,
+	},
+	/// Param name changed.
+	ParamRenamed {
+		/// Changed entry.
+		entry: Entry,
+		/// Whether the param is an input or output.
+		location: ParamLocation,
+		/// Position of the param.
+		index: usize,
+		/// Old param name.
+		old: String,
+		/// New param name.
+		new: String,
+	},
+}
+
+impl Change {
+	/// Returns true if the change breaks existing callers, indexers or decoders.
+	///
+	/// Added entries, renamed params and state mutability changes that do not remove
+	/// an ability of the function, eg. `nonpayable` to `payable`, are compatible.
+	pub fn is_breaking(&self) -> bool {
+		match *self {
+			Change::Added(_) | Change::ParamRenamed { .. } => false,
+			Change::Removed(_) | Change::InputsChanged { .. } => true,
+			Change::OutputsChanged { .. } | Change::EventLayoutChanged { .. } => true,
+			Change::StateMutabilityChanged { old, new, .. } => {
+				(old.is_payable() && !new.is_payable()) || (old.is_constant() && !new.is_constant())
+			},
+		}
+	}
+}
+
+fn write_types(types: &[ParamType]) -> String {
+	types.iter().map(Writer::write).collect::<Vec<_>>().join(",")
+}
+
+fn write_state_mutability(state_mutability: StateMutability) -> &'static str {
+	match state_mutability {
+		StateMutability::Pure => "pure",
+		StateMutability::View => "view",
+		StateMutability::NonPayable => "nonpayable",
+		StateMutability::Payable => "payable",
+	}
+}
+
+impl fmt::Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Change::Added(ref entry) => write!(f, "added {}", entry),
+			Change::Removed(ref entry) => write!(f, "removed {}", entry),
+			Change::InputsChanged { ref entry, ref old, ref new } => {
+				write!(f, "{} takes ({}) instead of ({})", entry, write_types(new), write_types(old))
+			},
+			Change::OutputsChanged { ref entry, ref old, ref new } => {
+				write!(f, "{} returns ({}) instead of ({})", entry, write_types(new), write_types(old))
+			},
+			Change::StateMutabilityChanged { ref entry, old, new } => {
+				write!(f, "{} is {} instead of {}", entry, write_state_mutability(new), write_state_mutability(old))
+			},
+			Change::EventLayoutChanged { ref entry } => write!(f, "{} has different indexed params", entry),
+			Change::ParamRenamed { ref entry, location, index, ref old, ref new } => {
+				let location = match location {
+					ParamLocation::Input => "input",
+					ParamLocation::Output => "output",
+				};
+				write!(f, "{} {} {} renamed from `{}` to `{}`", entry, location, index, old, new)
+			},
+		}
+	}
+}
+
+/// Interface changes between two versions of a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diff {
+	/// All changes, ordered by entry.
+	pub changes: Vec<Change>,
+}
+
+impl Diff {
+	/// Compares old and new version of a contract.
+	///
+	/// Functions and errors are matched by selector and events by signature. Removed and added
+	/// entries with the same name and number of inputs are reported as changed input types,
+	/// unless there are more overloads matching them.
+	pub fn new(old: &Contract, new: &Contract) -> Diff {
+		let mut changes = vec![];
+
+		match (old.constructor(), new.constructor()) {
+			(Some(_), None) => changes.push(Change::Removed(Entry::Constructor)),
+			(None, Some(_)) => changes.push(Change::Added(Entry::Constructor)),
+			(Some(old), Some(new)) => {
+				let (old_inputs, new_inputs) = (param_types(&old.inputs), param_types(&new.inputs));
+				if old_inputs != new_inputs {
+					changes.push(Change::InputsChanged { entry: Entry::Constructor, old: old_inputs, new: new_inputs });
+				}
+				if old.state_mutability != new.state_mutability {
+					changes.push(Change::StateMutabilityChanged {
+						entry: Entry::Constructor,
+						old: old.state_mutability,
+						new: new.state_mutability,
+					});
+				}
+				diff_names(&Entry::Constructor, ParamLocation::Input, &param_names(&old.inputs), &param_names(&new.inputs), &mut changes);
+			},
+			(None, None) => (),
+		}
+
+		let old_functions = by_signature(old.functions(), Function::signature);
+		let new_functions = by_signature(new.functions(), Function::signature);
+		let inputs = |f: &Function| (f.name.clone(), param_types(&f.inputs));
+		diff_entries(&old_functions, &new_functions, Entry::Function, inputs, &mut changes, |entry, old, new, changes| {
+			let (old_outputs, new_outputs) = (old.output_param_types(), new.output_param_types());
+			if old_outputs != new_outputs {
+				changes.push(Change::OutputsChanged { entry: entry.clone(), old: old_outputs, new: new_outputs });
+			}
+			if old.state_mutability != new.state_mutability {
+				changes.push(Change::StateMutabilityChanged {
+					entry: entry.clone(),
+					old: old.state_mutability,
+					new: new.state_mutability,
+				});
+			}
+			diff_names(entry, ParamLocation::Input, &param_names(&old.inputs), &param_names(&new.inputs), changes);
+			diff_names(entry, ParamLocation::Output, &param_names(&old.outputs), &param_names(&new.outputs), changes);
+		});
+
+		let old_events = by_signature(old.events(), Event::signature_string);
+		let new_events = by_signature(new.events(), Event::signature_string);
+		let inputs = |e: &Event| (e.name.clone(), e.inputs.iter().map(|p| p.kind.clone()).collect());
+		diff_entries(&old_events, &new_events, Entry::Event, inputs, &mut changes, |entry, old, new, changes| {
+			let indexed = |e: &Event| e.inputs.iter().map(|p| p.indexed).collect::<Vec<_>>();
+			if old.anonymous != new.anonymous || indexed(old) != indexed(new) {
+				changes.push(Change::EventLayoutChanged { entry: entry.clone() });
+			}
+			let names = |e: &Event| e.inputs.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+			diff_names(entry, ParamLocation::Input, &names(old), &names(new), changes);
+		});
+
+		let old_errors = by_signature(old.errors(), AbiError::signature);
+		let new_errors = by_signature(new.errors(), AbiError::signature);
+		let inputs = |e: &AbiError| (e.name.clone(), param_types(&e.inputs));
+		diff_entries(&old_errors, &new_errors, Entry::Error, inputs, &mut changes, |entry, old, new, changes| {
+			diff_names(entry, ParamLocation::Input, &param_names(&old.inputs), &param_names(&new.inputs), changes);
+		});
+
+		match (old.fallback(), new.fallback()) {
+			(Some(_), None) => changes.push(Change::Removed(Entry::Fallback)),
+			(None, Some(_)) => changes.push(Change::Added(Entry::Fallback)),
+			(Some(old), Some(new)) if old.state_mutability != new.state_mutability => {
+				changes.push(Change::StateMutabilityChanged {
+					entry: Entry::Fallback,
+					old: old.state_mutability,
+					new: new.state_mutability,
+				});
+			},
+			_ => (),
+		}
+
+		match (old.receive(), new.receive()) {
+			(Some(_), None) => changes.push(Change::Removed(Entry::Receive)),
+			(None, Some(_)) => changes.push(Change::Added(Entry::Receive)),
+			_ => (),
+		}
+
+		Diff { changes }
+	}
+
+	/// Returns true if there are no changes.
+	pub fn is_empty(&self) -> bool {
+		self.changes.is_empty()
+	}
+
+	/// Returns true if any change breaks existing callers, indexers or decoders.
+	pub fn is_breaking(&self) -> bool {
+		self.changes.iter().any(Change::is_breaking)
+	}
+
+	/// Returns changes that break existing callers, indexers or decoders.
+	pub fn breaking(&self) -> Vec<&Change> {
+		self.changes.iter().filter(|c| c.is_breaking()).collect()
+	}
+
+	/// Returns backwards compatible changes.
+	pub fn compatible(&self) -> Vec<&Change> {
+		self.changes.iter().filter(|c| !c.is_breaking()).collect()
+	}
+}
+
+fn by_signature<'a, T, I, F>(entries: I, signature: F) -> BTreeMap<String, &'a T>
+	where I: Iterator<Item = &'a T>, F: Fn(&T) -> String
+{
+	entries.map(|e| (signature(e), e)).collect()
+}
+
+fn param_names(params: &[Param]) -> Vec<String> {
+	params.iter().map(|p| p.name.clone()).collect()
+}
+
+fn param_types(params: &[Param]) -> Vec<ParamType> {
+	params.iter().map(|p| p.kind.clone()).collect()
+}
+
+fn diff_entries<T, E, I, F>(
+	old: &BTreeMap<String, &T>,
+	new: &BTreeMap<String, &T>,
+	entry: E,
+	inputs: I,
+	changes: &mut Vec<Change>,
+	diff_entry: F,
+) where E: Fn(String) -> Entry, I: Fn(&T) -> (String, Vec<ParamType>), F: Fn(&Entry, &T, &T, &mut Vec<Change>) {
+	// removed and added entries with the same name and arity, paired only if there is exactly one of each
+	let mut candidates: BTreeMap<_, (Vec<String>, Vec<String>)> = BTreeMap::new();
+	for (signature, old_entry) in old.iter().filter(|&(signature, _)| !new.contains_key(signature)) {
+		let (name, types) = inputs(old_entry);
+		candidates.entry((name, types.len())).or_default().0.push(signature.clone());
+	}
+	for (signature, new_entry) in new.iter().filter(|&(signature, _)| !old.contains_key(signature)) {
+		let (name, types) = inputs(new_entry);
+		candidates.entry((name, types.len())).or_default().1.push(signature.clone());
+	}
+	let pairs = candidates.into_iter()
+		.filter(|&(_, (ref removed, ref added))| removed.len() == 1 && added.len() == 1)
+		.map(|(_, (mut removed, mut added))| (removed.remove(0), added.remove(0)))
+		.collect::<BTreeMap<_, _>>();
+
+	for (signature, old_entry) in old {
+		let id = entry(signature.clone());
+		match (new.get(signature), pairs.get(signature)) {
+			(Some(new_entry), _) => diff_entry(&id, old_entry, new_entry, changes),
+			(None, Some(new_signature)) => {
+				let new_entry = new[new_signature];
+				changes.push(Change::InputsChanged {
+					entry: id.clone(),
+					old: inputs(old_entry).1,
+					new: inputs(new_entry).1,
+				});
+				diff_entry(&id, old_entry, new_entry, changes);
+			},
+			(None, None) => changes.push(Change::Removed(id)),
+		}
+	}
+
+	let paired = pairs.values().collect::<Vec<_>>();
+	for signature in new.keys().filter(|signature| !old.contains_key(*signature) && !paired.contains(signature)) {
+		changes.push(Change::Added(entry(signature.clone())));
+	}
+}
+
+fn diff_names(entry: &Entry, location: ParamLocation, old: &[String], new: &[String], changes: &mut Vec<Change>) {
+	for (index, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+		if old != new {
+			changes.push(Change::ParamRenamed {
+				entry: entry.clone(),
+				location,
+				index,
+				old: old.clone(),
+				new: new.clone(),
+			});
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {Contract, Entry, ParamType, StateMutability};
+	use super::{Change, Diff, ParamLocation};
+
+	#[test]
+	fn diff_contracts() {
+		let old = Contract::from_human_readable(&[
+			"constructor(address owner)",
+			"function transfer(address to, uint256 amount) returns (bool)",
+			"function balanceOf(address owner) view returns (uint256)",
+			"function deposit() payable",
+			"function burn(uint256 amount)",
+			"function approve(address spender, uint256 amount) returns (bool)",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"error Unauthorized()",
+			"error InsufficientBalance(uint256 needed)",
+			"receive() external payable",
+		]).unwrap();
+		let new = Contract::from_human_readable(&[
+			"constructor(address owner, uint256 supply) payable",
+			"function transfer(address recipient, uint256 amount) returns (bool)",
+			"function balanceOf(address owner) view returns (uint128)",
+			"function deposit()",
+			"function mint(uint256 amount)",
+			"function approve(address spender, uint128 amount) returns (bool)",
+			"event Transfer(address indexed from, address to, uint256 value)",
+			"error Paused()",
+		]).unwrap();
+
+		let diff = Diff::new(&old, &new);
+		assert_eq!(diff.breaking(), vec![
+			&Change::InputsChanged {
+				entry: Entry::Constructor,
+				old: vec![ParamType::Address],
+				new: vec![ParamType::Address, ParamType::Uint(256)],
+			},
+			&Change::InputsChanged {
+				entry: Entry::Function("approve(address,uint256)".to_owned()),
+				old: vec![ParamType::Address, ParamType::Uint(256)],
+				new: vec![ParamType::Address, ParamType::Uint(128)],
+			},
+			&Change::OutputsChanged {
+				entry: Entry::Function("balanceOf(address)".to_owned()),
+				old: vec![ParamType::Uint(256)],
+				new: vec![ParamType::Uint(128)],
+			},
+			&Change::Removed(Entry::Function("burn(uint256)".to_owned())),
+			&Change::StateMutabilityChanged {
+				entry: Entry::Function("deposit()".to_owned()),
+				old: StateMutability::Payable,
+				new: StateMutability::NonPayable,
+			},
+			&Change::EventLayoutChanged { entry: Entry::Event("Transfer(address,address,uint256)".to_owned()) },
+			&Change::Removed(Entry::Error("InsufficientBalance(uint256)".to_owned())),
+			&Change::Removed(Entry::Error("Unauthorized()".to_owned())),
+			&Change::Removed(Entry::Receive),
+		]);
+		assert_eq!(diff.compatible(), vec![
+			&Change::StateMutabilityChanged {
+				entry: Entry::Constructor,
+				old: StateMutability::NonPayable,
+				new: StateMutability::Payable,
+			},
+			&Change::ParamRenamed {
+				entry: Entry::Function("transfer(address,uint256)".to_owned()),
+				location: ParamLocation::Input,
+				index: 0,
+				old: "to".to_owned(),
+				new: "recipient".to_owned(),
+			},
+			&Change::Added(Entry::Function("mint(uint256)".to_owned())),
+			&Change::Added(Entry::Error("Paused()".to_owned())),
+		]);
+		assert!(diff.is_breaking());
+		assert_eq!(diff.changes[0].to_string(), "constructor takes (address,uint256) instead of (address)");
+		assert_eq!(diff.breaking()[1].to_string(), "function approve(address,uint256) takes (address,uint128) instead of (address,uint256)");
+		assert_eq!(diff.compatible()[1].to_string(), "function transfer(address,uint256) input 0 renamed from `to` to `recipient`");
+
+		assert!(Diff::new(&old, &old).is_empty());
+	}
+
+	#[test]
+	fn ambiguous_overloads_are_not_paired() {
+		let old = Contract::from_human_readable(&["function f(uint8 a)", "function f(uint16 a)"]).unwrap();
+		let new = Contract::from_human_readable(&["function f(uint32 a)"]).unwrap();
+
+		assert_eq!(Diff::new(&old, &new).changes, vec![
+			Change::Removed(Entry::Function("f(uint16)".to_owned())),
+			Change::Removed(Entry::Function("f(uint8)".to_owned())),
+			Change::Added(Entry::Function("f(uint32)".to_owned())),
+		]);
+	}
+
+	#[test]
+	fn state_mutability_compatibility() {
+		let change = |old, new| Change::StateMutabilityChanged { entry: Entry::Fallback, old, new };
+		assert!(!change(StateMutability::NonPayable, StateMutability::Payable).is_breaking());
+		assert!(!change(StateMutability::View, StateMutability::Pure).is_breaking());
+		assert!(!change(StateMutability::NonPayable, StateMutability::View).is_breaking());
+		assert!(change(StateMutability::View, StateMutability::NonPayable).is_breaking());
+		assert!(change(StateMutability::Payable, StateMutability::NonPayable).is_breaking());
+	}
+}
@@ -164,6 +164,14 @@ fn encode_token(token: &Token) -> Mediate {
 		Token::FixedBytes(ref bytes) => Mediate::Raw(pad_fixed_bytes(bytes)),
 		Token::Int(int) => Mediate::Raw(vec![int.into()]),
 		Token::Uint(uint) => Mediate::Raw(vec![uint.into()]),
+		Token::Fixed(fixed) => Mediate::Raw(vec![fixed.into()]),
+		Token::UFixed(ufixed) => Mediate::Raw(vec![ufixed.into()]),
+		Token::Function(ref address, ref selector) => {
+			let mut padded = [0u8; 32];
+			padded[..20].copy_from_slice(address.as_ref());
+			padded[20..24].copy_from_slice(selector);
+			Mediate::Raw(vec![padded])
+		}
 		Token::Bool(b) => {
 			let mut value = [0u8; 32];
 			if b {
@@ -533,6 +541,23 @@ mod tests {
 		assert_eq!(encoded, expected);
 	}
 
+	#[test]
+	fn encode_fixed() {
+		let encoded = encode(&vec![Token::UFixed(150.into()), Token::Fixed(!::Uint::zero())]);
+		let expected = hex!("
+			0000000000000000000000000000000000000000000000000000000000000096
+			ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff
+		").to_vec();
+		assert_eq!(encoded, expected);
+	}
+
+	#[test]
+	fn encode_function() {
+		let encoded = encode(&vec![Token::Function([0x11u8; 20].into(), [0xa9, 0x05, 0x9c, 0xbb])]);
+		let expected = hex!("1111111111111111111111111111111111111111a9059cbb0000000000000000");
+		assert_eq!(encoded, expected);
+	}
+
 	#[test]
 	fn encode_bool() {
 		let encoded = encode(&vec![Token::Bool(true)]);
@@ -0,0 +1,33 @@
+//! Abi entry identifier.
+
+use std::fmt;
+
+/// Abi entry, identified by its canonical signature.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Entry {
+	/// Constructor.
+	Constructor,
+	/// Function, eg. `transfer(address,uint256)`.
+	Function(String),
+	/// Event, eg. `Transfer(address,address,uint256)`.
+	Event(String),
+	/// Custom error, eg. `InsufficientBalance(uint256,uint256)`.
+	Error(String),
+	/// Fallback function.
+	Fallback,
+	/// Receive function.
+	Receive,
+}
+
+impl fmt::Display for Entry {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Entry::Constructor => write!(f, "constructor"),
+			Entry::Function(ref signature) => write!(f, "function {}", signature),
+			Entry::Event(ref signature) => write!(f, "event {}", signature),
+			Entry::Error(ref signature) => write!(f, "error {}", signature),
+			Entry::Fallback => write!(f, "fallback"),
+			Entry::Receive => write!(f, "receive"),
+		}
+	}
+}
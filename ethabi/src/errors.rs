@@ -3,6 +3,7 @@
 
 use std::{num, string};
 use {serde_json, hex};
+use revert::Revert;
 
 error_chain! {
 	foreign_links {
@@ -18,9 +19,29 @@ error_chain! {
 			display("Invalid name `{}`", name),
 		}
 
+		InvalidType(name: String, position: usize, expected: String) {
+			description("Invalid type"),
+			display("Invalid type `{}`, expected {} at position {}", name, expected, position),
+		}
+
+		AmbiguousName(name: String) {
+			description("Ambiguous name"),
+			display("Ambiguous name `{}`, use a signature or selector instead", name),
+		}
+
+		MergeConflict(existing: String, new: String) {
+			description("Conflicting abi entries"),
+			display("Conflicting abi entries `{}` and `{}`", existing, new),
+		}
+
 		InvalidData {
 			description("Invalid data"),
 			display("Invalid data"),
 		}
+
+		Reverted(revert: Revert) {
+			description("Call reverted"),
+			display("{}", revert),
+		}
 	}
 }
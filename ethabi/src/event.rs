@@ -1,8 +1,8 @@
 //! Contract event.
 
-use std::collections::HashMap;
 use tiny_keccak::keccak256;
-use signature::long_signature;
+use signature::{long_signature, signature_string};
+use human_readable;
 use {
 	Log, Hash, RawLog, LogParam, RawTopicFilter, TopicFilter,
 	Topic, ParamType, EventParam, encode, decode, Token,
@@ -10,7 +10,7 @@ use {
 };
 
 /// Contract event.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Event {
 	/// Event name.
 	pub name: String,
@@ -21,17 +21,10 @@ pub struct Event {
 }
 
 impl Event {
-	/// Returns names of all params.
-	fn params_names(&self) -> Vec<String> {
-		self.inputs.iter()
-			.map(|p| p.name.clone())
-			.collect()
-	}
-
 	/// Returns types of all params.
 	fn param_types(&self) -> Vec<ParamType> {
 		self.inputs.iter()
-			.map(|p| p.kind.clone())
+			.map(|p| p.true_type())
 			.collect()
 	}
 
@@ -48,6 +41,16 @@ impl Event {
 		long_signature(&self.name, &self.param_types())
 	}
 
+	/// Returns canonical event signature, eg. `Transfer(address,address,uint256)`.
+	pub fn signature_string(&self) -> String {
+		signature_string(&self.name, &self.param_types())
+	}
+
+	/// Returns human-readable event, eg. `event Transfer(address indexed from, address indexed to, uint256 value)`.
+	pub fn human_readable(&self) -> String {
+		human_readable::format_event(self)
+	}
+
 	/// Creates topic filter
 	pub fn filter(&self, raw: RawTopicFilter) -> Result<TopicFilter> {
 		fn convert_token(token: Token, kind: &ParamType) -> Result<Hash> {
@@ -83,10 +86,10 @@ impl Event {
 			}
 		}
 
-		let kinds: Vec<_> = self.indexed_params(true).into_iter().map(|param| param.kind).collect();
+		let kinds: Vec<_> = self.indexed_params(true).iter().map(EventParam::true_type).collect();
 		let result = if self.anonymous {
 			TopicFilter {
-				topic0: convert_topic(raw.topic0, kinds.get(0))?,
+				topic0: convert_topic(raw.topic0, kinds.first())?,
 				topic1: convert_topic(raw.topic1, kinds.get(1))?,
 				topic2: convert_topic(raw.topic2, kinds.get(2))?,
 				topic3: Topic::Any,
@@ -94,7 +97,7 @@ impl Event {
 		} else {
 			TopicFilter {
 				topic0: Topic::This(self.signature()),
-				topic1: convert_topic(raw.topic0, kinds.get(0))?,
+				topic1: convert_topic(raw.topic0, kinds.first())?,
 				topic2: convert_topic(raw.topic1, kinds.get(1))?,
 				topic3: convert_topic(raw.topic2, kinds.get(2))?,
 			}
@@ -116,52 +119,41 @@ impl Event {
 			0
 		} else {
 			// verify
-			let event_signature = topics.get(0).ok_or(ErrorKind::InvalidData)?;
+			let event_signature = topics.first().ok_or(ErrorKind::InvalidData)?;
 			if event_signature != &self.signature() {
 				return Err(ErrorKind::InvalidData.into());
 			}
 			1
 		};
 
-		let topic_types = topic_params.iter()
-			.map(|p| p.kind.clone())
-			.collect::<Vec<ParamType>>();
-
-		let flat_topics = topics.into_iter()
-			.skip(to_skip)
-			.flat_map(|t| t.as_ref().to_vec())
-			.collect::<Vec<u8>>();
-
-		let topic_tokens = try!(decode(&topic_types, &flat_topics));
-
-		// topic may be only a 32 bytes encoded token
-		if topic_tokens.len() != topics_len - to_skip {
+		if topic_params.len() != topics_len - to_skip {
 			return Err(ErrorKind::InvalidData.into());
 		}
 
-		let topics_named_tokens = topic_params.into_iter()
-			.map(|p| p.name)
-			.zip(topic_tokens.into_iter());
+		// indexed strings, bytes, arrays and tuples are stored as keccak256 hash of their encoding
+		let mut topic_tokens = topic_params.iter()
+			.zip(topics.into_iter().skip(to_skip))
+			.map(|(p, topic)| match p.true_type() {
+				ParamType::String | ParamType::Bytes | ParamType::Array(_) | ParamType::FixedArray(_, _) | ParamType::Tuple(_) => {
+					Ok(Token::FixedBytes(topic.as_ref().to_vec()))
+				},
+				kind => decode(&[kind], topic.as_ref()).map(|mut tokens| tokens.remove(0)),
+			})
+			.collect::<Result<Vec<Token>>>()?
+			.into_iter();
 
 		let data_types = data_params.iter()
-			.map(|p| p.kind.clone())
+			.map(EventParam::true_type)
 			.collect::<Vec<ParamType>>();
 
-		let data_tokens = try!(decode(&data_types, &data));
-
-		let data_named_tokens = data_params.into_iter()
-			.map(|p| p.name)
-			.zip(data_tokens.into_iter());
-
-		let named_tokens = topics_named_tokens
-			.chain(data_named_tokens)
-			.collect::<HashMap<String, Token>>();
+		let mut data_tokens = try!(decode(&data_types, &data)).into_iter();
 
-		let decoded_params = self.params_names()
-			.into_iter()
-			.map(|name| LogParam {
-				name: name.clone(),
-				value: named_tokens[&name].clone()
+		// hashed topics have no fields to name
+		let decoded_params = self.inputs.iter()
+			.map(|p| if p.indexed {
+				LogParam::new(p.name.clone(), topic_tokens.next().expect("one token per indexed param; qed"))
+			} else {
+				LogParam::with_components(p.name.clone(), data_tokens.next().expect("one token per data param; qed"), &p.components)
 			})
 			.collect();
 
@@ -176,10 +168,12 @@ impl Event {
 #[cfg(test)]
 mod tests {
 	use hex::FromHex;
+	use serde_json;
 	use token::Token;
 	use signature::long_signature;
 	use log::{RawLog, Log};
-	use {EventParam, ParamType, Event, LogParam};
+	use human_readable;
+	use {encode, EventParam, Hash, Param, ParamType, Event, LogParam};
 
 	#[test]
 	fn test_decoding_event() {
@@ -189,18 +183,26 @@ mod tests {
 				name: "a".to_owned(),
 				kind: ParamType::Int(256),
 				indexed: false,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "b".to_owned(),
 				kind: ParamType::Int(256),
 				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "c".to_owned(),
 				kind: ParamType::Address,
 				indexed: false,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "d".to_owned(),
 				kind: ParamType::Address,
 				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}],
 			anonymous: false,
 		};
@@ -223,6 +225,132 @@ mod tests {
 			("b".to_owned(), Token::Int("0000000000000000000000000000000000000000000000000000000000000002".into())),
 			("c".to_owned(), Token::Address("2222222222222222222222222222222222222222".parse().unwrap())),
 			("d".to_owned(), Token::Address("1111111111111111111111111111111111111111".parse().unwrap())),
-		].into_iter().map(|(name, value)| LogParam { name, value }).collect::<Vec<_>>()});
+		].into_iter().map(|(name, value)| LogParam::new(name, value)).collect::<Vec<_>>()});
+	}
+
+	#[test]
+	fn test_event_signature_string() {
+		let event = Event {
+			name: "Transfer".to_owned(),
+			inputs: vec![EventParam {
+				name: "from".to_owned(),
+				kind: ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}, EventParam {
+				name: "".to_owned(),
+				kind: ParamType::Uint(256),
+				indexed: false,
+				internal_type: None,
+				components: vec![],
+			}],
+			anonymous: true,
+		};
+
+		assert_eq!(event.signature_string(), "Transfer(address,uint256)");
+		assert_eq!(event.signature(), long_signature("Transfer", &[ParamType::Address, ParamType::Uint(256)]));
+		assert_eq!(event.human_readable(), "event Transfer(address indexed from, uint256) anonymous");
+	}
+
+	#[test]
+	fn test_event_signature_resolves_components() {
+		let event = Event {
+			name: "Filled".to_owned(),
+			inputs: vec![EventParam {
+				name: "order".to_owned(),
+				kind: ParamType::Tuple(vec![]),
+				indexed: false,
+				internal_type: None,
+				components: vec![
+					Param { name: "maker".to_owned(), kind: ParamType::Address, internal_type: None, components: vec![] },
+					Param { name: "amount".to_owned(), kind: ParamType::Uint(256), internal_type: None, components: vec![] },
+				],
+			}],
+			anonymous: false,
+		};
+
+		assert_eq!(event.signature_string(), "Filled((address,uint256))");
+	}
+
+	#[test]
+	fn test_decoding_event_with_tuple() {
+		let s = r#"{
+			"name": "OrderFilled",
+			"inputs": [{
+				"name": "id",
+				"type": "bytes32",
+				"indexed": true
+			}, {
+				"name": "order",
+				"type": "tuple",
+				"indexed": false,
+				"components": [
+					{ "name": "maker", "type": "address" },
+					{ "name": "amount", "type": "uint256" }
+				]
+			}],
+			"anonymous": false
+		}"#;
+		let event: Event = serde_json::from_str(s).unwrap();
+
+		assert_eq!(event.signature_string(), "OrderFilled(bytes32,(address,uint256))");
+		assert_eq!(event.human_readable(), "event OrderFilled(bytes32 indexed id, tuple(address maker, uint256 amount) order)");
+
+		let log = RawLog {
+			topics: vec![
+				long_signature("OrderFilled", &[ParamType::FixedBytes(32), ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)])]),
+				"0000000000000000000000000000000000000000000000000000000000000001".parse().unwrap(),
+			],
+			data: hex!("
+				0000000000000000000000002222222222222222222222222222222222222222
+				0000000000000000000000000000000000000000000000000000000000000003
+			").to_vec(),
+		};
+		let result = event.parse_log(log).unwrap();
+
+		let maker = Token::Address("2222222222222222222222222222222222222222".parse().unwrap());
+		assert_eq!(result.params[1], LogParam {
+			name: "order".to_owned(),
+			value: Token::Tuple(vec![maker.clone(), Token::Uint(3.into())]),
+			components: vec![
+				LogParam { name: "maker".to_owned(), value: maker, components: vec![] },
+				LogParam { name: "amount".to_owned(), value: Token::Uint(3.into()), components: vec![] },
+			],
+		});
+	}
+
+	#[test]
+	fn test_decoding_event_with_indexed_tuple() {
+		let event = human_readable::parse_event(
+			"event OrderPlaced(tuple(address maker, uint256 amount) indexed order, string indexed note, tuple(address maker, tuple(uint8 kind) meta)[] fills)"
+		).unwrap();
+		let order_hash: Hash = "1111111111111111111111111111111111111111111111111111111111111111".parse().unwrap();
+		let note_hash: Hash = "2222222222222222222222222222222222222222222222222222222222222222".parse().unwrap();
+		let maker = Token::Address("3333333333333333333333333333333333333333".parse().unwrap());
+		let fill = Token::Tuple(vec![maker.clone(), Token::Tuple(vec![Token::Uint(1.into())])]);
+
+		let log = RawLog {
+			topics: vec![event.signature(), order_hash, note_hash],
+			data: encode(&[Token::Array(vec![fill.clone()])]),
+		};
+		let result = event.parse_log(log).unwrap();
+
+		assert_eq!(result.params[0].value, Token::FixedBytes(order_hash.as_ref().to_vec()));
+		assert!(result.params[0].components.is_empty());
+		assert_eq!(result.params[1].value, Token::FixedBytes(note_hash.as_ref().to_vec()));
+		assert_eq!(result.params[2].value, Token::Array(vec![fill.clone()]));
+		assert_eq!(result.params[2].components, vec![LogParam {
+			name: "0".to_owned(),
+			value: fill,
+			components: vec![
+				LogParam { name: "maker".to_owned(), value: maker, components: vec![] },
+				LogParam {
+					name: "meta".to_owned(),
+					value: Token::Tuple(vec![Token::Uint(1.into())]),
+					components: vec![LogParam { name: "kind".to_owned(), value: Token::Uint(1.into()), components: vec![] }],
+				},
+			],
+		}]);
 	}
 }
@@ -1,23 +1,98 @@
 //! Event param specification.
 
-use {ParamType};
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeStruct;
+use param::{json_type, resolve_tuple, tuple_components};
+use {InternalType, Param, ParamType};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EventParamIr {
+	/// Param name.
+	pub name: String,
+	/// Param type.
+	#[serde(rename="type")]
+	pub kind: ParamType,
+	/// Indexed flag. If true, param is used to build block bloom.
+	pub indexed: bool,
+	/// Solidity type, eg. `struct Market.Order`.
+	#[serde(default, rename="internalType")]
+	pub internal_type: Option<String>,
+	/// Components type for tuple.
+	#[serde(default)]
+	pub components: Vec<Param>,
+}
 
 /// Event param specification.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "EventParamIr")]
 pub struct EventParam {
 	/// Param name.
 	pub name: String,
 	/// Param type.
-	#[serde(rename="type")]
 	pub kind: ParamType,
 	/// Indexed flag. If true, param is used to build block bloom.
 	pub indexed: bool,
+	/// Solidity type, eg. `struct Market.Order`.
+	pub internal_type: Option<String>,
+	/// Components type for tuple.
+	pub components: Vec<Param>,
+}
+
+impl EventParam {
+	/// Returns param type with tuples, including tuples nested in arrays, resolved from components.
+	pub fn true_type(&self) -> ParamType {
+		resolve_tuple(&self.kind, &self.components)
+	}
+
+	/// Returns parsed solidity type of the param, if known.
+	pub fn parsed_internal_type(&self) -> Option<InternalType> {
+		self.internal_type.as_ref().map(|t| InternalType::parse(t))
+	}
+}
+
+impl From<EventParamIr> for EventParam {
+	fn from(p: EventParamIr) -> Self {
+		EventParam {
+			name: p.name,
+			kind: resolve_tuple(&p.kind, &p.components),
+			indexed: p.indexed,
+			internal_type: p.internal_type,
+			components: p.components,
+		}
+	}
+}
+
+impl Serialize for EventParam {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		let generated;
+		let components = if self.components.is_empty() {
+			generated = tuple_components(&self.kind);
+			&generated
+		} else {
+			&self.components
+		};
+		let has_components = !components.is_empty();
+		let mut param = serializer.serialize_struct("EventParam", 5)?;
+		param.serialize_field("name", &self.name)?;
+		param.serialize_field("type", &json_type(&self.kind, has_components))?;
+		match self.internal_type {
+			Some(ref internal_type) => param.serialize_field("internalType", internal_type)?,
+			None => param.skip_field("internalType")?,
+		}
+		param.serialize_field("indexed", &self.indexed)?;
+		if has_components {
+			param.serialize_field("components", components)?;
+		} else {
+			param.skip_field("components")?;
+		}
+		param.end()
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use {EventParam, ParamType};
+	use {EventParam, Param, ParamType};
 
 	#[test]
 	fn event_param_deserialization() {
@@ -33,6 +108,52 @@ mod tests {
 			name: "foo".to_owned(),
 			kind: ParamType::Address,
 			indexed: true,
+			internal_type: None,
+			components: vec![],
+		});
+	}
+
+	#[test]
+	fn event_param_tuple_deserialization() {
+		let s = r#"{
+			"name": "orders",
+			"type": "tuple[]",
+			"indexed": false,
+			"components": [
+				{ "name": "maker", "type": "address" },
+				{ "name": "amount", "type": "uint256" }
+			]
+		}"#;
+
+		let deserialized: EventParam = serde_json::from_str(s).unwrap();
+
+		assert_eq!(deserialized, EventParam {
+			name: "orders".to_owned(),
+			kind: ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)]))),
+			indexed: false,
+			internal_type: None,
+			components: vec![
+				Param { name: "maker".to_owned(), kind: ParamType::Address, internal_type: None, components: vec![] },
+				Param { name: "amount".to_owned(), kind: ParamType::Uint(256), internal_type: None, components: vec![] },
+			],
 		});
+
+		let serialized: EventParam = serde_json::from_str(&serde_json::to_string(&deserialized).unwrap()).unwrap();
+		assert_eq!(serialized, deserialized);
+	}
+
+	#[test]
+	fn event_param_true_type() {
+		let param = EventParam {
+			name: "order".to_owned(),
+			kind: ParamType::Tuple(vec![]),
+			indexed: false,
+			internal_type: None,
+			components: vec![
+				Param { name: "maker".to_owned(), kind: ParamType::Address, internal_type: None, components: vec![] },
+			],
+		};
+
+		assert_eq!(param.true_type(), ParamType::Tuple(vec![ParamType::Address]));
 	}
 }
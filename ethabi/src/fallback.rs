@@ -0,0 +1,51 @@
+//! Contract fallback function.
+
+use StateMutability;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FallbackIr {
+	/// Legacy payable flag.
+	#[serde(default)]
+	pub payable: Option<bool>,
+	/// State mutability, emitted by solc >= 0.4.16.
+	#[serde(default, rename = "stateMutability")]
+	pub state_mutability: Option<StateMutability>,
+}
+
+/// Contract fallback function specification.
+///
+/// Executed when the calldata does not match any function selector, and for plain
+/// ether transfers if the contract has no receive function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "FallbackIr")]
+pub struct Fallback {
+	/// Fallback state mutability, either `NonPayable` or `Payable`.
+	#[serde(rename = "stateMutability")]
+	pub state_mutability: StateMutability,
+}
+
+impl From<FallbackIr> for Fallback {
+	fn from(f: FallbackIr) -> Self {
+		Fallback {
+			state_mutability: StateMutability::resolve(f.state_mutability, None, f.payable),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use {Fallback, StateMutability};
+
+	#[test]
+	fn fallback_deserialization() {
+		let fallback: Fallback = serde_json::from_str(r#"{ "type": "fallback", "payable": true }"#).unwrap();
+		assert_eq!(fallback.state_mutability, StateMutability::Payable);
+
+		let fallback: Fallback = serde_json::from_str(r#"{ "type": "fallback", "stateMutability": "nonpayable" }"#).unwrap();
+		assert_eq!(fallback.state_mutability, StateMutability::NonPayable);
+
+		let fallback: Fallback = serde_json::from_str(r#"{ "type": "fallback" }"#).unwrap();
+		assert_eq!(fallback.state_mutability, StateMutability::NonPayable);
+	}
+}
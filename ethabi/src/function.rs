@@ -1,10 +1,32 @@
 //! Contract function call builder.
 
-use signature::short_signature;
-use {decode, encode, Bytes, ErrorKind, Param, ParamType, Result, Token};
+use signature::{short_signature, signature_string};
+use human_readable;
+use {decode, encode, Bytes, ErrorKind, NamedToken, Param, ParamType, Result, Revert, StateMutability, Token};
 
-/// Contract function specification.
+/// Function as written in json abi, with legacy mutability flags.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
+struct FunctionIr {
+	/// Function name.
+	name: String,
+	/// Function input.
+	inputs: Vec<Param>,
+	/// Function output.
+	outputs: Vec<Param>,
+	/// Legacy constant flag.
+	#[serde(default)]
+	constant: Option<bool>,
+	/// Legacy payable flag.
+	#[serde(default)]
+	payable: Option<bool>,
+	/// State mutability, emitted by solc >= 0.4.16.
+	#[serde(default, rename = "stateMutability")]
+	state_mutability: Option<StateMutability>,
+}
+
+/// Contract function specification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "FunctionIr")]
 pub struct Function {
 	/// Function name.
 	pub name: String,
@@ -12,20 +34,52 @@ pub struct Function {
 	pub inputs: Vec<Param>,
 	/// Function output.
 	pub outputs: Vec<Param>,
-	/// Constant function.
-	#[serde(default)]
+	/// Constant function, derived from `state_mutability` when loaded from json.
 	pub constant: bool,
+	/// Function state mutability.
+	#[serde(rename = "stateMutability")]
+	pub state_mutability: StateMutability,
+}
+
+impl From<FunctionIr> for Function {
+	fn from(f: FunctionIr) -> Self {
+		let state_mutability = StateMutability::resolve(f.state_mutability, f.constant, f.payable);
+
+		Function {
+			name: f.name,
+			inputs: f.inputs,
+			outputs: f.outputs,
+			// legacy `constant` flag is only used when `stateMutability` is missing
+			constant: state_mutability.is_constant(),
+			state_mutability,
+		}
+	}
 }
 
 impl Function {
 	/// Returns all input params of given function.
 	fn input_param_types(&self) -> Vec<ParamType> {
-		self.inputs.iter().map(|p| p.true_type().clone()).collect()
+		self.inputs.iter().map(|p| p.true_type()).collect()
 	}
 
 	/// Returns all output params of given function.
-	fn output_param_types(&self) -> Vec<ParamType> {
-		self.outputs.iter().map(|p| p.kind.clone()).collect()
+	pub(crate) fn output_param_types(&self) -> Vec<ParamType> {
+		self.outputs.iter().map(|p| p.true_type()).collect()
+	}
+
+	/// Returns canonical function signature, eg. `transfer(address,uint256)`.
+	pub fn signature(&self) -> String {
+		signature_string(&self.name, &self.input_param_types())
+	}
+
+	/// Returns 4-byte function selector.
+	pub fn selector(&self) -> [u8; 4] {
+		short_signature(&self.name, &self.input_param_types())
+	}
+
+	/// Returns human-readable function, eg. `function balanceOf(address owner) view returns (uint256)`.
+	pub fn human_readable(&self) -> String {
+		human_readable::format_function(self)
 	}
 
 	/// Prepares ABI function call with given input params.
@@ -36,20 +90,59 @@ impl Function {
 			return Err(ErrorKind::InvalidData.into());
 		}
 
-		let signed = short_signature(&self.name, &params).to_vec();
-		let encoded = encode(tokens);
-		Ok(signed.into_iter().chain(encoded.into_iter()).collect())
+		let mut result = Vec::with_capacity(4 + ParamType::min_encoded_size(&params));
+		result.extend_from_slice(&self.selector());
+		result.extend(encode(tokens));
+		Ok(result)
+	}
+
+	/// Parses the ABI function call, including the selector, to list of named tokens.
+	pub fn decode_input(&self, data: &[u8]) -> Result<Vec<NamedToken>> {
+		if data.len() < 4 || data[..4] != self.selector() {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		let tokens = decode(&self.input_param_types(), &data[4..])?;
+		Ok(NamedToken::from_params(&self.inputs, tokens))
+	}
+
+	/// Prepares ABI function output with given output params, eg. to mock a call result.
+	pub fn encode_output(&self, tokens: &[Token]) -> Result<Bytes> {
+		if !Token::types_check(tokens, &self.output_param_types()) {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		Ok(encode(tokens))
 	}
 
 	/// Parses the ABI function output to list of tokens.
 	pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>> {
 		decode(&self.output_param_types(), &data)
 	}
+
+	/// Parses the ABI function output to list of tokens, or fails with `ErrorKind::Reverted`
+	/// if the data is a revert reason.
+	///
+	/// Revert data is recognized by the `Error(string)` and `Panic(uint256)` selectors, by being
+	/// empty while outputs are expected, or by a selector followed by 32-byte words, eg. a custom
+	/// error, which can be decoded with `Contract::decode_revert`. Other data that is not a valid
+	/// output fails with the decoding error.
+	pub fn decode_output_or_revert(&self, data: &[u8]) -> Result<Vec<Token>> {
+		let reverted = Revert::decode(data).is_some() ||
+			(data.is_empty() && !self.outputs.is_empty()) ||
+			data.len() % 32 == 4;
+		if reverted {
+			return Err(ErrorKind::Reverted(Revert::from_data(data)).into());
+		}
+
+		self.decode_output(data)
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use {Function, Param, ParamType, Token};
+	use serde_json;
+	use {ErrorKind, Function, I256, NamedToken, Param, ParamType, PanicCode, Result, Revert, StateMutability, Token};
 
 	#[test]
 	fn test_function_encode_call() {
@@ -59,16 +152,19 @@ mod tests {
 				Param {
 					name: "a".to_owned(),
 					kind: ParamType::Uint(32),
+					internal_type: None,
 					components: vec![],
 				},
 				Param {
 					name: "b".to_owned(),
 					kind: ParamType::Bool,
+					internal_type: None,
 					components: vec![],
 				},
 			],
 			outputs: vec![],
 			constant: false,
+			state_mutability: StateMutability::NonPayable,
 		};
 
 		let func = Function::from(interface);
@@ -80,6 +176,195 @@ mod tests {
 		let expected = hex!("cdcd77c000000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001").to_vec();
 		assert_eq!(encoded, expected);
 	}
+
+	#[test]
+	fn test_function_encode_narrow_int() {
+		let func = Function {
+			name: "baz".to_owned(),
+			inputs: vec![Param {
+				name: "a".to_owned(),
+				kind: ParamType::Int(8),
+				internal_type: None,
+				components: vec![],
+			}],
+			outputs: vec![],
+			constant: false,
+			state_mutability: StateMutability::NonPayable,
+		};
+
+		let encoded = func.encode_input(&[Token::Int(I256::from(-128i32).into())]).unwrap();
+		assert_eq!(encoded[4..].to_vec(), hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80").to_vec());
+
+		assert!(func.encode_input(&[Token::Int(I256::from(-129i32).into())]).is_err());
+		assert!(func.encode_input(&[Token::Int(I256::from(128i32).into())]).is_err());
+	}
+
+	#[test]
+	fn test_function_decode_output_or_revert() {
+		let func = Function {
+			name: "balanceOf".to_owned(),
+			inputs: vec![],
+			outputs: vec![Param {
+				name: "".to_owned(),
+				kind: ParamType::Uint(256),
+				internal_type: None,
+				components: vec![],
+			}],
+			constant: true,
+			state_mutability: StateMutability::View,
+		};
+
+		fn revert(result: Result<Vec<Token>>) -> Revert {
+			match *result.unwrap_err().kind() {
+				ErrorKind::Reverted(ref revert) => revert.clone(),
+				ref other => panic!("unexpected error: {:?}", other),
+			}
+		}
+
+		let output = hex!("0000000000000000000000000000000000000000000000000000000000000045");
+		assert_eq!(func.decode_output_or_revert(&output).unwrap(), vec![Token::Uint(69.into())]);
+
+		let panic = hex!("
+			4e487b71
+			0000000000000000000000000000000000000000000000000000000000000012
+		");
+		assert_eq!(revert(func.decode_output_or_revert(&panic)), Revert::Panic(PanicCode::DivisionByZero));
+		assert_eq!(revert(func.decode_output_or_revert(&[])), Revert::Empty);
+
+		let custom = hex!("
+			cf479181
+			0000000000000000000000000000000000000000000000000000000000000001
+		");
+		assert_eq!(revert(func.decode_output_or_revert(&custom)), Revert::Unknown(custom.to_vec()));
+
+		// malformed output is not a revert
+		match *func.decode_output_or_revert(&output[..31]).unwrap_err().kind() {
+			ErrorKind::InvalidData => (),
+			ref other => panic!("unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_function_state_mutability() {
+		fn deserialize(fields: &str) -> Function {
+			let s = format!(r#"{{ "name": "foo", "inputs": [], "outputs": [] {} }}"#, fields);
+			serde_json::from_str(&s).unwrap()
+		}
+
+		let function = deserialize(r#", "stateMutability": "pure""#);
+		assert_eq!(function.state_mutability, StateMutability::Pure);
+		assert!(function.constant);
+
+		let function = deserialize(r#", "constant": false, "payable": false, "stateMutability": "view""#);
+		assert_eq!(function.state_mutability, StateMutability::View);
+		assert!(function.constant);
+
+		let function = deserialize(r#", "constant": true, "stateMutability": "nonpayable""#);
+		assert_eq!(function.state_mutability, StateMutability::NonPayable);
+		assert!(!function.constant);
+
+		let function = deserialize(r#", "constant": true"#);
+		assert_eq!(function.state_mutability, StateMutability::View);
+		assert!(function.constant);
+
+		let function = deserialize(r#", "payable": true"#);
+		assert_eq!(function.state_mutability, StateMutability::Payable);
+
+		let function = deserialize("");
+		assert_eq!(function.state_mutability, StateMutability::NonPayable);
+		assert!(!function.constant);
+	}
+
+	#[test]
+	fn test_function_signature() {
+		let s = r#"{
+			"name": "swap",
+			"inputs": [
+				{ "name": "path", "type": "address[]" },
+				{
+					"name": "order",
+					"type": "tuple",
+					"components": [
+						{ "name": "amount", "type": "uint256" },
+						{ "name": "", "type": "bytes32[2]" }
+					]
+				}
+			],
+			"outputs": [{ "name": "", "type": "uint256" }],
+			"stateMutability": "payable"
+		}"#;
+		let function: Function = serde_json::from_str(s).unwrap();
+
+		assert_eq!(function.signature(), "swap(address[],(uint256,bytes32[2]))");
+		assert_eq!(function.selector().to_vec(), function.encode_input(&[
+			Token::Array(vec![]),
+			Token::Tuple(vec![Token::Uint(1.into()), Token::FixedArray(vec![Token::FixedBytes(vec![0; 32]), Token::FixedBytes(vec![0; 32])])]),
+		]).unwrap()[..4].to_vec());
+		assert_eq!(function.human_readable(), "function swap(address[] path, tuple(uint256 amount, bytes32[2]) order) payable returns (uint256)");
+	}
+
+	#[test]
+	fn test_function_decode_input() {
+		let s = r#"{
+			"name": "fill",
+			"inputs": [
+				{ "name": "id", "type": "uint256" },
+				{
+					"name": "orders",
+					"type": "tuple[]",
+					"components": [
+						{ "name": "maker", "type": "address" },
+						{ "name": "data", "type": "bytes" }
+					]
+				}
+			],
+			"outputs": []
+		}"#;
+		let function: Function = serde_json::from_str(s).unwrap();
+		let orders = Token::Array(vec![
+			Token::Tuple(vec![Token::Address([0x11u8; 20].into()), Token::Bytes(vec![1, 2, 3])]),
+		]);
+		let encoded = function.encode_input(&[Token::Uint(7.into()), orders.clone()]).unwrap();
+
+		assert_eq!(function.decode_input(&encoded).unwrap(), vec![
+			NamedToken { name: "id".to_owned(), value: Token::Uint(7.into()) },
+			NamedToken { name: "orders".to_owned(), value: orders },
+		]);
+
+		let mut wrong_selector = encoded.clone();
+		wrong_selector[0] ^= 0xff;
+		assert!(function.decode_input(&wrong_selector).is_err());
+		assert!(function.decode_input(&encoded[..3]).is_err());
+	}
+
+	#[test]
+	fn test_function_encode_output() {
+		let s = r#"{
+			"name": "position",
+			"inputs": [],
+			"outputs": [
+				{
+					"name": "",
+					"type": "tuple",
+					"components": [
+						{ "name": "owner", "type": "address" },
+						{ "name": "amounts", "type": "uint256[]" }
+					]
+				},
+				{ "name": "open", "type": "bool" }
+			]
+		}"#;
+		let function: Function = serde_json::from_str(s).unwrap();
+		let tokens = vec![
+			Token::Tuple(vec![Token::Address([0x11u8; 20].into()), Token::Array(vec![Token::Uint(5.into())])]),
+			Token::Bool(true),
+		];
+
+		let encoded = function.encode_output(&tokens).unwrap();
+		assert_eq!(function.decode_output(&encoded).unwrap(), tokens);
+		assert!(function.encode_output(&tokens[..1]).is_err());
+		assert!(function.encode_output(&[Token::Tuple(vec![Token::Bool(true)]), Token::Bool(true)]).is_err());
+	}
 }
 
 
@@ -91,20 +376,24 @@ fn test_function_encode_call_with_tuple() {
 			Param {
 				name: "bar".to_owned(),
 				kind: ParamType::Address,
+				internal_type: None,
 				components: vec![],
 			},
 			Param {
 				name: "foo".to_owned(),
 				kind: ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes]),
+				internal_type: None,
 				components: vec![
 					Param {
 						name: "bar".to_owned(),
 						kind: ParamType::Bool,
+						internal_type: None,
 						components: vec![],
 					},
 					Param {
 						name: "baz".to_owned(),
 						kind: ParamType::Bytes,
+						internal_type: None,
 						components: vec![],
 					},
 				],
@@ -112,6 +401,7 @@ fn test_function_encode_call_with_tuple() {
 		],
 		outputs: vec![],
 		constant: false,
+		state_mutability: StateMutability::NonPayable,
 	};
 
 	let func = Function::from(interface);
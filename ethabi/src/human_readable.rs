@@ -0,0 +1,558 @@
+//! Human-readable abi parser and formatter.
+//!
+//! Parses and formats Solidity-like signatures, eg.
+//!
+//! ```text
+//! function balanceOf(address owner) view returns (uint256)
+//! event Transfer(address indexed from, address indexed to, uint256 value)
+//! constructor(string name, string symbol) payable
+//! error InsufficientBalance(uint256 available, uint256 required)
+//! function fill((address maker, uint256 amount)[] orders) external
+//! ```
+
+use operation::Operation;
+use param_type::{Reader, Writer};
+use {
+	AbiError, Constructor, ErrorKind, Event, EventParam, Fallback, Function, Param, ParamType, Receive,
+	Result, StateMutability,
+};
+
+/// Parses human-readable function, eg. `function balanceOf(address owner) view returns (uint256)`.
+pub fn parse_function(line: &str) -> Result<Function> {
+	match parse_operation(line)? {
+		Operation::Function(function) => Ok(function),
+		_ => Err(ErrorKind::InvalidType(line.to_owned(), 0, "`function`".to_owned()).into()),
+	}
+}
+
+/// Parses human-readable event, eg. `event Transfer(address indexed from, address indexed to, uint256 value)`.
+pub fn parse_event(line: &str) -> Result<Event> {
+	match parse_operation(line)? {
+		Operation::Event(event) => Ok(event),
+		_ => Err(ErrorKind::InvalidType(line.to_owned(), 0, "`event`".to_owned()).into()),
+	}
+}
+
+/// Parses human-readable constructor, eg. `constructor(string name) payable`.
+pub fn parse_constructor(line: &str) -> Result<Constructor> {
+	match parse_operation(line)? {
+		Operation::Constructor(constructor) => Ok(constructor),
+		_ => Err(ErrorKind::InvalidType(line.to_owned(), 0, "`constructor`".to_owned()).into()),
+	}
+}
+
+/// Parses human-readable custom error, eg. `error InsufficientBalance(uint256 available, uint256 required)`.
+pub fn parse_error(line: &str) -> Result<AbiError> {
+	match parse_operation(line)? {
+		Operation::Error(error) => Ok(error),
+		_ => Err(ErrorKind::InvalidType(line.to_owned(), 0, "`error`".to_owned()).into()),
+	}
+}
+
+/// Parses any human-readable abi entry.
+pub(crate) fn parse_operation(line: &str) -> Result<Operation> {
+	let mut parser = Parser { input: line.trim().trim_end_matches(';'), pos: 0 };
+	let operation = parser.operation()?;
+	parser.skip_whitespace();
+	if parser.pos != parser.input.len() {
+		return Err(parser.error("end of input"));
+	}
+	Ok(operation)
+}
+
+/// Formats any abi entry as human-readable abi.
+pub(crate) fn format_operation(operation: &Operation) -> String {
+	match *operation {
+		Operation::Constructor(ref constructor) => format_constructor(constructor),
+		Operation::Function(ref function) => format_function(function),
+		Operation::Event(ref event) => format_event(event),
+		Operation::Error(ref error) => format_error(error),
+		Operation::Fallback(ref fallback) => format!("fallback() external{}", format_state_mutability(fallback.state_mutability)),
+		Operation::Receive(ref receive) => format!("receive() external{}", format_state_mutability(receive.state_mutability)),
+	}
+}
+
+/// Formats function, eg. `function balanceOf(address owner) view returns (uint256)`.
+pub(crate) fn format_function(function: &Function) -> String {
+	let mut result = format!("function {}({}){}", function.name, format_params(&function.inputs), format_state_mutability(function.state_mutability));
+	if !function.outputs.is_empty() {
+		result.push_str(&format!(" returns ({})", format_params(&function.outputs)));
+	}
+	result
+}
+
+/// Formats event, eg. `event Transfer(address indexed from, address indexed to, uint256 value)`.
+pub(crate) fn format_event(event: &Event) -> String {
+	let params = event.inputs.iter()
+		.map(|param| {
+			let indexed = if param.indexed {
+				" indexed"
+			} else {
+				""
+			};
+			format_named(format!("{}{}", format_param_type(&param.kind, &param.components), indexed), &param.name)
+		})
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	let anonymous = if event.anonymous {
+		" anonymous"
+	} else {
+		""
+	};
+	format!("event {}({}){}", event.name, params, anonymous)
+}
+
+/// Formats constructor, eg. `constructor(string name) payable`.
+pub(crate) fn format_constructor(constructor: &Constructor) -> String {
+	format!("constructor({}){}", format_params(&constructor.inputs), format_state_mutability(constructor.state_mutability))
+}
+
+/// Formats custom error, eg. `error InsufficientBalance(uint256 available, uint256 required)`.
+pub(crate) fn format_error(error: &AbiError) -> String {
+	format!("error {}({})", error.name, format_params(&error.inputs))
+}
+
+/// Formats state mutability keyword preceded by a space. Nonpayable is the default, so it is omitted.
+fn format_state_mutability(state_mutability: StateMutability) -> &'static str {
+	match state_mutability {
+		StateMutability::Pure => " pure",
+		StateMutability::View => " view",
+		StateMutability::NonPayable => "",
+		StateMutability::Payable => " payable",
+	}
+}
+
+fn format_params(params: &[Param]) -> String {
+	params.iter()
+		.map(|param| format_named(format_param_type(&param.kind, &param.components), &param.name))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+fn format_named(kind: String, name: &str) -> String {
+	if name.is_empty() {
+		kind
+	} else {
+		format!("{} {}", kind, name)
+	}
+}
+
+/// Formats param type, writing tuples described by components with their names, eg. `tuple(address maker, uint256 amount)[]`.
+fn format_param_type(kind: &ParamType, components: &[Param]) -> String {
+	match *kind {
+		ParamType::Tuple(_) if !components.is_empty() => format!("tuple({})", format_params(components)),
+		ParamType::Array(ref param) => format!("{}[]", format_param_type(param, components)),
+		ParamType::FixedArray(ref param, len) => format!("{}[{}]", format_param_type(param, components), len),
+		_ => Writer::write(kind),
+	}
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn error(&self, expected: &str) -> ::Error {
+		ErrorKind::InvalidType(self.input.to_owned(), self.pos, expected.to_owned()).into()
+	}
+
+	/// Moves position of a type error returned by `Reader` by `offset`, so that it points into the whole line.
+	fn type_error(&self, error: ::Error, offset: usize) -> ::Error {
+		match *error.kind() {
+			ErrorKind::InvalidType(_, pos, ref expected) => {
+				ErrorKind::InvalidType(self.input.to_owned(), offset + pos, expected.clone()).into()
+			},
+			_ => error,
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		let rest = &self.input[self.pos..];
+		self.pos += rest.len() - rest.trim_start().len();
+	}
+
+	fn peek(&mut self) -> Option<char> {
+		self.skip_whitespace();
+		self.input[self.pos..].chars().next()
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		if self.peek() == Some(c) {
+			self.pos += c.len_utf8();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn expect(&mut self, c: char) -> Result<()> {
+		if self.eat(c) {
+			Ok(())
+		} else {
+			Err(self.error(&format!("`{}`", c)))
+		}
+	}
+
+	/// Reads identifier, eg. a keyword, type or param name. Identifiers do not start with a digit.
+	fn identifier(&mut self) -> Option<&'a str> {
+		self.skip_whitespace();
+		let rest = &self.input[self.pos..];
+		if rest.starts_with(|c: char| c.is_numeric()) {
+			return None;
+		}
+
+		let len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).unwrap_or(rest.len());
+		if len == 0 {
+			None
+		} else {
+			self.pos += len;
+			Some(&rest[..len])
+		}
+	}
+
+	/// Reads identifier only if it is equal to the given keyword.
+	fn keyword(&mut self, keyword: &str) -> bool {
+		let pos = self.pos;
+		match self.identifier() {
+			Some(ident) if ident == keyword => true,
+			_ => {
+				self.pos = pos;
+				false
+			}
+		}
+	}
+
+	fn name(&mut self) -> Result<String> {
+		self.identifier().map(ToOwned::to_owned).ok_or_else(|| self.error("name"))
+	}
+
+	fn operation(&mut self) -> Result<Operation> {
+		let pos = self.pos;
+		let operation = match self.identifier() {
+			Some("function") => {
+				let name = self.name()?;
+				let inputs = self.params()?;
+				let state_mutability = self.modifiers()?;
+				let outputs = if self.keyword("returns") {
+					self.params()?
+				} else {
+					vec![]
+				};
+				Operation::Function(Function {
+					name,
+					inputs,
+					outputs,
+					constant: state_mutability.is_constant(),
+					state_mutability,
+				})
+			},
+			Some("event") => {
+				let name = self.name()?;
+				let inputs = self.event_params()?;
+				let anonymous = self.keyword("anonymous");
+				Operation::Event(Event { name, inputs, anonymous })
+			},
+			Some("error") => {
+				let name = self.name()?;
+				let inputs = self.params()?;
+				Operation::Error(AbiError { name, inputs })
+			},
+			Some("constructor") => {
+				let inputs = self.params()?;
+				let state_mutability = self.modifiers()?;
+				Operation::Constructor(Constructor { inputs, state_mutability })
+			},
+			Some("fallback") => {
+				self.expect('(')?;
+				self.expect(')')?;
+				let state_mutability = self.modifiers()?;
+				Operation::Fallback(Fallback { state_mutability })
+			},
+			Some("receive") => {
+				self.expect('(')?;
+				self.expect(')')?;
+				let state_mutability = self.modifiers()?;
+				Operation::Receive(Receive { state_mutability })
+			},
+			_ => {
+				self.pos = pos;
+				return Err(self.error("`function`, `event`, `error`, `constructor`, `fallback` or `receive`"));
+			}
+		};
+		Ok(operation)
+	}
+
+	/// Reads visibility and state mutability keywords, returning the state mutability.
+	fn modifiers(&mut self) -> Result<StateMutability> {
+		let mut state_mutability = StateMutability::NonPayable;
+		loop {
+			let pos = self.pos;
+			match self.identifier() {
+				Some("external") | Some("public") | Some("internal") | Some("private") |
+				Some("virtual") | Some("override") => (),
+				Some("pure") => state_mutability = StateMutability::Pure,
+				Some("view") | Some("constant") => state_mutability = StateMutability::View,
+				Some("payable") => state_mutability = StateMutability::Payable,
+				Some("nonpayable") => state_mutability = StateMutability::NonPayable,
+				_ => {
+					self.pos = pos;
+					return Ok(state_mutability);
+				}
+			}
+		}
+	}
+
+	/// Reads comma separated list of items enclosed in parentheses.
+	fn list<T, F>(&mut self, mut item: F) -> Result<Vec<T>> where F: FnMut(&mut Self) -> Result<T> {
+		self.expect('(')?;
+		let mut result = vec![];
+		if self.eat(')') {
+			return Ok(result);
+		}
+
+		loop {
+			result.push(item(self)?);
+			if self.eat(')') {
+				return Ok(result);
+			}
+			self.expect(',')?;
+		}
+	}
+
+	fn params(&mut self) -> Result<Vec<Param>> {
+		self.list(|parser| {
+			let (kind, components) = parser.param_type()?;
+			parser.data_location();
+			let name = parser.identifier().unwrap_or("").to_owned();
+			Ok(Param { name, kind, internal_type: None, components })
+		})
+	}
+
+	fn event_params(&mut self) -> Result<Vec<EventParam>> {
+		self.list(|parser| {
+			let (kind, components) = parser.param_type()?;
+			let indexed = parser.keyword("indexed");
+			let name = parser.identifier().unwrap_or("").to_owned();
+			Ok(EventParam { name, kind, indexed, internal_type: None, components })
+		})
+	}
+
+	fn data_location(&mut self) {
+		let _ = self.keyword("memory") || self.keyword("calldata") || self.keyword("storage");
+	}
+
+	/// Reads param type, returning it together with the components of tuple types.
+	fn param_type(&mut self) -> Result<(ParamType, Vec<Param>)> {
+		self.skip_whitespace();
+		let pos = self.pos;
+		let (kind, components) = match self.identifier() {
+			Some("tuple") if self.peek() == Some('(') => self.tuple()?,
+			Some(name) => {
+				let kind = Reader::read(name).map_err(|e| self.type_error(e, pos))?;
+				// `address payable` is encoded as `address`
+				if kind == ParamType::Address {
+					self.keyword("payable");
+				}
+				(kind, vec![])
+			},
+			None if self.peek() == Some('(') => self.tuple()?,
+			None => return Err(self.error("param type")),
+		};
+
+		let (kind, len) = Reader::read_suffixes(&self.input[self.pos..], kind).map_err(|e| self.type_error(e, self.pos))?;
+		self.pos += len;
+		Ok((kind, components))
+	}
+
+	fn tuple(&mut self) -> Result<(ParamType, Vec<Param>)> {
+		let components = self.params()?;
+		let kind = ParamType::Tuple(components.iter().map(|c| c.kind.clone()).collect());
+		Ok((kind, components))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_constructor, parse_error, parse_event, parse_function};
+	use {AbiError, Contract, ErrorKind, Event, EventParam, Function, Param, ParamType, StateMutability};
+
+	fn param(name: &str, kind: ParamType) -> Param {
+		Param { name: name.to_owned(), kind, internal_type: None, components: vec![] }
+	}
+
+	#[test]
+	fn parse_view_function() {
+		let function = parse_function("function balanceOf(address owner) view returns (uint256)").unwrap();
+		assert_eq!(function, Function {
+			name: "balanceOf".to_owned(),
+			inputs: vec![param("owner", ParamType::Address)],
+			outputs: vec![param("", ParamType::Uint(256))],
+			constant: true,
+			state_mutability: StateMutability::View,
+		});
+	}
+
+	#[test]
+	fn parse_function_with_modifiers_and_locations() {
+		let function = parse_function("function transfer(address to, uint amount, bytes calldata data) external payable returns (bool success);").unwrap();
+		assert_eq!(function, Function {
+			name: "transfer".to_owned(),
+			inputs: vec![
+				param("to", ParamType::Address),
+				param("amount", ParamType::Uint(256)),
+				param("data", ParamType::Bytes),
+			],
+			outputs: vec![param("success", ParamType::Bool)],
+			constant: false,
+			state_mutability: StateMutability::Payable,
+		});
+	}
+
+	#[test]
+	fn parse_function_with_inline_tuples() {
+		let function = parse_function("function fill(tuple(address maker, uint256[2] amounts) order, (bool, string name)[] extras)").unwrap();
+		let order = vec![
+			param("maker", ParamType::Address),
+			param("amounts", ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2)),
+		];
+		let extras = vec![
+			param("", ParamType::Bool),
+			param("name", ParamType::String),
+		];
+
+		assert_eq!(function.inputs, vec![
+			Param {
+				name: "order".to_owned(),
+				kind: ParamType::Tuple(vec![ParamType::Address, ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2)]),
+				internal_type: None,
+				components: order,
+			},
+			Param {
+				name: "extras".to_owned(),
+				kind: ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::String]))),
+				internal_type: None,
+				components: extras,
+			},
+		]);
+		assert_eq!(function.signature(), "fill((address,uint256[2]),(bool,string)[])");
+	}
+
+	#[test]
+	fn parse_event_with_indexed_params() {
+		let event = parse_event("event Transfer(address indexed from, address indexed to, uint256 value)").unwrap();
+		assert_eq!(event, Event {
+			name: "Transfer".to_owned(),
+			inputs: vec![
+				EventParam { name: "from".to_owned(), kind: ParamType::Address, indexed: true, internal_type: None, components: vec![] },
+				EventParam { name: "to".to_owned(), kind: ParamType::Address, indexed: true, internal_type: None, components: vec![] },
+				EventParam { name: "value".to_owned(), kind: ParamType::Uint(256), indexed: false, internal_type: None, components: vec![] },
+			],
+			anonymous: false,
+		});
+
+		assert!(parse_event("event Ping(uint256) anonymous").unwrap().anonymous);
+	}
+
+	#[test]
+	fn parse_constructor_and_error() {
+		let constructor = parse_constructor("constructor(string name) payable").unwrap();
+		assert_eq!(constructor.inputs, vec![param("name", ParamType::String)]);
+		assert_eq!(constructor.state_mutability, StateMutability::Payable);
+
+		let error = parse_error("error InsufficientBalance(uint256 available, uint256 required)").unwrap();
+		assert_eq!(error, AbiError {
+			name: "InsufficientBalance".to_owned(),
+			inputs: vec![param("available", ParamType::Uint(256)), param("required", ParamType::Uint(256))],
+		});
+	}
+
+	#[test]
+	fn parse_invalid_lines() {
+		assert!(parse_function("function foo(uin256)").is_err());
+		assert!(parse_function("function foo(uint7)").is_err());
+		assert!(parse_function("function foo(uint256").is_err());
+		assert!(parse_function("function foo(uint256) returns").is_err());
+		assert!(parse_function("function foo() view extra").is_err());
+		assert!(parse_function("event Foo()").is_err());
+		assert!(parse_event("struct Foo { uint256 a; }").is_err());
+	}
+
+	#[test]
+	fn parse_invalid_types() {
+		fn error(line: &str) -> (usize, String) {
+			match *parse_function(line).unwrap_err().kind() {
+				ErrorKind::InvalidType(ref input, position, ref expected) => {
+					assert_eq!(input, line);
+					(position, expected.clone())
+				},
+				ref other => panic!("unexpected error: {:?}", other),
+			}
+		}
+
+		assert_eq!(error("function foo(uint7 a)"), (17, "size between 8 and 256 in steps of 8".to_owned()));
+		assert_eq!(error("function foo(uin256 a)"), (13, "type".to_owned()));
+		assert_eq!(error("function foo(uint256[0] a)"), (21, "non-zero array size".to_owned()));
+		assert_eq!(error("function foo(bool[01] a)"), (18, "non-zero array size".to_owned()));
+		assert_eq!(error("function foo((bool, string)[2 a)"), (30, "`]`".to_owned()));
+		assert_eq!(error("function foo(uint 256)"), (18, "`,`".to_owned()));
+		assert_eq!(error("function 2foo()"), (9, "name".to_owned()));
+		assert_eq!(error("event Foo()"), (0, "`function`".to_owned()));
+	}
+
+	#[test]
+	fn parse_address_payable() {
+		let function = parse_function("function send(address payable to, address payable[] memory others, address payable)").unwrap();
+		assert_eq!(function.inputs, vec![
+			param("to", ParamType::Address),
+			param("others", ParamType::Array(Box::new(ParamType::Address))),
+			param("", ParamType::Address),
+		]);
+	}
+
+	#[test]
+	fn contract_from_human_readable() {
+		let contract = Contract::from_human_readable(&[
+			"constructor(address owner)",
+			"function balanceOf(address owner) view returns (uint256)",
+			"function transfer(address to, uint256 amount) returns (bool)",
+			"function transfer(address to, uint256 amount, bytes data) returns (bool)",
+			"",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"error Unauthorized()",
+			"receive() external payable",
+			"fallback() external",
+		]).unwrap();
+
+		assert!(contract.constructor().is_some());
+		assert_eq!(contract.functions().count(), 3);
+		assert_eq!(contract.overloads("transfer").count(), 2);
+		assert!(contract.event("Transfer").is_ok());
+		assert!(contract.error("Unauthorized").is_ok());
+		assert!(contract.accepts_plain_transfer());
+		assert!(!contract.accepts_unknown_selector(true));
+	}
+
+	#[test]
+	fn format_roundtrip() {
+		let lines = [
+			"constructor(string name) payable",
+			"function balanceOf(address owner) view returns (uint256)",
+			"function fill(tuple(address maker, uint256[2] amounts) order, tuple(bool, string name)[] extras) payable returns (bool success)",
+			"function touch()",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"event Ping(tuple(uint256, bool)[] indexed, bytes32) anonymous",
+			"error InsufficientBalance(uint256 available, uint256 required)",
+			"fallback() external payable",
+			"receive() external payable",
+		];
+
+		let contract = Contract::from_human_readable(&lines).unwrap();
+		let mut formatted = contract.to_human_readable();
+		let mut expected: Vec<_> = lines.iter().map(|line| line.to_string()).collect();
+		formatted.sort();
+		expected.sort();
+		assert_eq!(formatted, expected);
+	}
+}
@@ -0,0 +1,188 @@
+//! Signed 256-bit integer.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use {Error, ErrorKind, Uint};
+
+/// Signed 256-bit integer, stored in two's complement just like the value of `Token::Int`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct I256(Uint);
+
+impl I256 {
+	/// Interprets unsigned integer as two's complement, eg. the value of `Token::Int`.
+	pub fn from_raw(raw: Uint) -> Self {
+		I256(raw)
+	}
+
+	/// Returns two's complement representation.
+	pub fn into_raw(self) -> Uint {
+		self.0
+	}
+
+	/// Returns true if the number is negative.
+	pub fn is_negative(&self) -> bool {
+		self.0.bit(255)
+	}
+
+	/// Returns absolute value.
+	pub fn abs(&self) -> Uint {
+		if self.is_negative() {
+			negate(self.0)
+		} else {
+			self.0
+		}
+	}
+
+	/// Parses decimal number, eg. `-42`.
+	pub fn from_dec_str(value: &str) -> Result<Self, Error> {
+		let (negative, digits) = match value.chars().next() {
+			Some('-') => (true, &value[1..]),
+			Some('+') => (false, &value[1..]),
+			_ => (false, value),
+		};
+		if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		let abs = Uint::from_dec_str(digits).map_err(|_| ErrorKind::InvalidData)?;
+		let min = Uint::one() << 255;
+		match negative {
+			false if abs < min => Ok(I256(abs)),
+			true if abs <= min => Ok(I256(negate(abs))),
+			_ => Err(ErrorKind::InvalidData.into()),
+		}
+	}
+
+	/// Returns true if the number fits in `int<bits>`, ie. all bits above the sign bit of
+	/// `int<bits>` are equal to it.
+	pub fn fits(&self, bits: usize) -> bool {
+		if bits == 0 {
+			return false;
+		}
+		if bits >= 256 {
+			return true;
+		}
+		if self.is_negative() {
+			(!self.0) >> (bits - 1) == Uint::zero()
+		} else {
+			self.0 >> (bits - 1) == Uint::zero()
+		}
+	}
+}
+
+/// Two's complement negation.
+fn negate(value: Uint) -> Uint {
+	(!value).overflowing_add(Uint::one()).0
+}
+
+impl From<I256> for Uint {
+	fn from(int: I256) -> Self {
+		int.0
+	}
+}
+
+impl fmt::Display for I256 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_negative() {
+			write!(f, "-{}", self.abs())
+		} else {
+			write!(f, "{}", self.0)
+		}
+	}
+}
+
+impl FromStr for I256 {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		I256::from_dec_str(s)
+	}
+}
+
+macro_rules! impl_primitive {
+	($($t: ty, $bits: expr;)*) => {
+		$(
+			impl From<$t> for I256 {
+				fn from(value: $t) -> Self {
+					if value < 0 {
+						I256(!Uint::from(!value as u128))
+					} else {
+						I256(Uint::from(value as u128))
+					}
+				}
+			}
+
+			impl TryFrom<I256> for $t {
+				type Error = Error;
+
+				fn try_from(int: I256) -> Result<Self, Self::Error> {
+					if int.fits($bits) {
+						Ok(int.0.low_u128() as i128 as $t)
+					} else {
+						Err(ErrorKind::InvalidData.into())
+					}
+				}
+			}
+		)*
+	}
+}
+
+impl_primitive! {
+	i8, 8;
+	i16, 16;
+	i32, 32;
+	i64, 64;
+	i128, 128;
+}
+
+#[cfg(test)]
+mod tests {
+	use std::convert::TryFrom;
+	use Uint;
+	use super::I256;
+
+	#[test]
+	fn i256_from_primitive() {
+		assert_eq!(I256::from(0i32).into_raw(), Uint::zero());
+		assert_eq!(I256::from(-1i32).into_raw(), hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").into());
+		assert_eq!(I256::from(-2i8).into_raw(), hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe").into());
+		assert_eq!(I256::from(-256i64).into_raw(), hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00").into());
+		assert_eq!(I256::from(i128::min_value()).into_raw(), hex!("ffffffffffffffffffffffffffffffff80000000000000000000000000000000").into());
+		assert_eq!(I256::from(300i16).into_raw(), 300.into());
+	}
+
+	#[test]
+	fn i256_to_primitive() {
+		assert_eq!(i8::try_from(I256::from(-128i32)).unwrap(), -128);
+		assert!(i8::try_from(I256::from(-129i32)).is_err());
+		assert!(i8::try_from(I256::from(128i32)).is_err());
+		assert_eq!(i64::try_from(I256::from(-5i8)).unwrap(), -5);
+		assert_eq!(i128::try_from(I256::from(i128::max_value())).unwrap(), i128::max_value());
+		assert!(i128::try_from(I256::from_raw(Uint::one() << 200)).is_err());
+	}
+
+	#[test]
+	fn i256_decimal() {
+		assert_eq!(I256::from(-2i32).to_string(), "-2");
+		assert_eq!(I256::from(42i32).to_string(), "42");
+		assert_eq!("-2".parse::<I256>().unwrap(), I256::from(-2i32));
+		assert_eq!("+7".parse::<I256>().unwrap(), I256::from(7i32));
+
+		let min = "-57896044618658097711785492504343953926634992332820282019728792003956564819968";
+		assert_eq!(min.parse::<I256>().unwrap().into_raw(), Uint::one() << 255);
+		assert_eq!(min.parse::<I256>().unwrap().to_string(), min);
+		assert!("57896044618658097711785492504343953926634992332820282019728792003956564819968".parse::<I256>().is_err());
+		assert!("-".parse::<I256>().is_err());
+		assert!("1e3".parse::<I256>().is_err());
+	}
+
+	#[test]
+	fn i256_fits() {
+		assert!(I256::from(127i32).fits(8));
+		assert!(!I256::from(128i32).fits(8));
+		assert!(I256::from(-128i32).fits(8));
+		assert!(!I256::from(-129i32).fits(8));
+		assert!(I256::from_raw(Uint::max_value()).fits(256));
+	}
+}
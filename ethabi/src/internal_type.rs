@@ -0,0 +1,126 @@
+//! Solidity type of a param, as emitted by solc in `internalType` field.
+
+use param_type::Reader;
+
+/// Parsed `internalType` of a param, eg. `struct Market.Order`.
+///
+/// Array suffixes, eg. `[]` in `struct Market.Order[]`, are not part of the parsed name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InternalType {
+	/// Struct, eg. `struct Market.Order`.
+	Struct {
+		/// Name of the contract in which the struct is defined.
+		contract: Option<String>,
+		/// Struct name.
+		name: String,
+	},
+	/// Enum, eg. `enum Side`.
+	Enum {
+		/// Name of the contract in which the enum is defined.
+		contract: Option<String>,
+		/// Enum name.
+		name: String,
+	},
+	/// Contract or interface, eg. `contract IERC20`.
+	Contract(String),
+	/// User defined value type, eg. `Market.Price`.
+	UserDefined {
+		/// Name of the contract in which the type is defined.
+		contract: Option<String>,
+		/// Type name.
+		name: String,
+	},
+	/// Elementary type, eg. `address payable` or `uint256`.
+	Elementary(String),
+}
+
+impl InternalType {
+	/// Parses `internalType` field.
+	pub fn parse(internal_type: &str) -> InternalType {
+		let trimmed = strip_array_suffix(internal_type.trim());
+
+		if let Some(name) = strip_prefix(trimmed, "struct ") {
+			let (contract, name) = split_contract(name);
+			InternalType::Struct { contract, name }
+		} else if let Some(name) = strip_prefix(trimmed, "enum ") {
+			let (contract, name) = split_contract(name);
+			InternalType::Enum { contract, name }
+		} else if let Some(name) = strip_prefix(trimmed, "contract ") {
+			InternalType::Contract(name.to_owned())
+		} else if is_elementary(trimmed) {
+			InternalType::Elementary(trimmed.to_owned())
+		} else {
+			let (contract, name) = split_contract(trimmed);
+			InternalType::UserDefined { contract, name }
+		}
+	}
+
+	/// Returns name of the type without the contract, eg. `Order` for `struct Market.Order`.
+	pub fn name(&self) -> &str {
+		match *self {
+			InternalType::Struct { ref name, .. } |
+			InternalType::Enum { ref name, .. } |
+			InternalType::UserDefined { ref name, .. } |
+			InternalType::Contract(ref name) |
+			InternalType::Elementary(ref name) => name,
+		}
+	}
+}
+
+fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+	if s.starts_with(prefix) {
+		Some(s[prefix.len()..].trim())
+	} else {
+		None
+	}
+}
+
+fn strip_array_suffix(mut s: &str) -> &str {
+	while s.ends_with(']') {
+		match s.rfind('[') {
+			Some(pos) => s = s[..pos].trim_end(),
+			None => break,
+		}
+	}
+	s
+}
+
+fn split_contract(name: &str) -> (Option<String>, String) {
+	match name.rfind('.') {
+		Some(pos) => (Some(name[..pos].to_owned()), name[pos + 1..].to_owned()),
+		None => (None, name.to_owned()),
+	}
+}
+
+fn is_elementary(s: &str) -> bool {
+	s == "address payable" || s.starts_with("function") || Reader::read(s).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use InternalType;
+
+	#[test]
+	fn parse_internal_type() {
+		assert_eq!(InternalType::parse("struct Market.Order"), InternalType::Struct {
+			contract: Some("Market".to_owned()),
+			name: "Order".to_owned(),
+		});
+		assert_eq!(InternalType::parse("struct Order[][2]"), InternalType::Struct {
+			contract: None,
+			name: "Order".to_owned(),
+		});
+		assert_eq!(InternalType::parse("enum Side"), InternalType::Enum {
+			contract: None,
+			name: "Side".to_owned(),
+		});
+		assert_eq!(InternalType::parse("contract IERC20"), InternalType::Contract("IERC20".to_owned()));
+		assert_eq!(InternalType::parse("Market.Price"), InternalType::UserDefined {
+			contract: Some("Market".to_owned()),
+			name: "Price".to_owned(),
+		});
+		assert_eq!(InternalType::parse("address payable"), InternalType::Elementary("address payable".to_owned()));
+		assert_eq!(InternalType::parse("uint256[]"), InternalType::Elementary("uint256".to_owned()));
+		assert_eq!(InternalType::parse("struct Market.Order[]").name(), "Order");
+	}
+}
@@ -19,36 +19,65 @@ extern crate hex_literal;
 
 extern crate ethereum_types;
 
+pub mod human_readable;
 pub mod param_type;
 pub mod token;
+mod abi_error;
+mod artifact;
+mod collision;
 mod constructor;
 mod contract;
 mod decoder;
+mod diff;
 mod encoder;
+mod entry;
 mod errors;
 mod event;
 mod event_param;
+mod fallback;
 mod filter;
 mod function;
+mod int;
+mod internal_type;
 mod log;
+mod merge;
+mod natspec;
 mod operation;
 mod param;
+mod receive;
+mod revert;
 mod signature;
+mod state_mutability;
 mod util;
+mod validate;
 
 pub use param_type::ParamType;
 pub use constructor::Constructor;
-pub use contract::{Contract, Functions, Events};
-pub use token::Token;
+pub use abi_error::AbiError;
+pub use artifact::{Artifact, Bytecode, LinkReference, LinkReferences};
+pub use collision::{find_collisions, Clash, Collision, CollisionEntry};
+pub use contract::{Contract, Functions, Events, EventOverloads, AbiErrors, Overloads};
+pub use token::{Token, NamedToken};
 pub use errors::{Error, ErrorKind, Result, ResultExt};
 pub use encoder::encode;
 pub use decoder::decode;
+pub use diff::{Diff, Change, ParamLocation};
+pub use entry::Entry;
 pub use filter::{Topic, TopicFilter, RawTopicFilter};
 pub use function::Function;
+pub use int::I256;
+pub use internal_type::InternalType;
 pub use param::Param;
+pub use revert::{Revert, PanicCode};
+pub use state_mutability::StateMutability;
+pub use merge::MergePolicy;
+pub use natspec::Documentation;
 pub use log::{Log, RawLog, LogParam, ParseLog, LogFilter};
 pub use event::Event;
 pub use event_param::EventParam;
+pub use validate::{Diagnostic, DiagnosticKind};
+pub use fallback::Fallback;
+pub use receive::Receive;
 
 /// ABI address.
 pub type Address = ethereum_types::Address;
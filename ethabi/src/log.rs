@@ -1,4 +1,4 @@
-use {Hash, Token, Bytes, Result, TopicFilter};
+use {Hash, Param, Token, Bytes, Result, TopicFilter};
 
 /// Common filtering functions that are available for any event.
 pub trait LogFilter {
@@ -36,12 +36,50 @@ impl From<(Vec<Hash>, Bytes)> for RawLog {
 }
 
 /// Decoded log param.
+///
+/// Use `LogParam::new` rather than a struct literal, it keeps working when fields are added.
 #[derive(Debug, PartialEq, Clone)]
 pub struct LogParam {
 	/// Decoded log name.
 	pub name: String,
 	/// Decoded log value.
 	pub value: Token,
+	/// Named fields of a decoded tuple, or elements of a decoded array of tuples named by their index.
+	/// Empty for other types.
+	pub components: Vec<LogParam>,
+}
+
+impl LogParam {
+	/// Creates log param without named components.
+	pub fn new(name: String, value: Token) -> LogParam {
+		LogParam {
+			name,
+			value,
+			components: vec![],
+		}
+	}
+
+	/// Names decoded token after its param, including the fields of nested tuples.
+	pub(crate) fn with_components(name: String, value: Token, components: &[Param]) -> LogParam {
+		let named = match value {
+			_ if components.is_empty() => vec![],
+			Token::Tuple(ref fields) => components.iter()
+				.zip(fields.iter())
+				.map(|(param, field)| LogParam::with_components(param.name.clone(), field.clone(), &param.components))
+				.collect(),
+			Token::Array(ref elements) | Token::FixedArray(ref elements) => elements.iter()
+				.enumerate()
+				.map(|(index, element)| LogParam::with_components(index.to_string(), element.clone(), components))
+				.collect(),
+			_ => vec![],
+		};
+
+		LogParam {
+			name,
+			value,
+			components: named,
+		}
+	}
 }
 
 /// Decoded log.
@@ -0,0 +1,173 @@
+//! Merging of several contracts into one interface, eg. a proxy with its implementation
+//! or all facets of an EIP-2535 diamond.
+
+use std::collections::{BTreeMap, HashMap};
+use operation::Operation;
+use human_readable;
+use {Contract, ErrorKind, Hash, Result};
+
+/// What to do with differing abi entries sharing a selector, topic or name.
+///
+/// Identical entries are always merged into one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+	/// Fail with `ErrorKind::MergeConflict`.
+	#[default]
+	Strict,
+	/// Keep the entry of the contract merged first.
+	KeepFirst,
+	/// Keep the entry of the contract merged last.
+	KeepLast,
+}
+
+/// Identifies abi entries that can not coexist in one contract.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+	Constructor,
+	Fallback,
+	Receive,
+	Function([u8; 4]),
+	Error([u8; 4]),
+	// event signature, ie. the first topic of non-anonymous events
+	Event(Hash),
+}
+
+fn key(operation: &Operation) -> Key {
+	match *operation {
+		Operation::Constructor(_) => Key::Constructor,
+		Operation::Fallback(_) => Key::Fallback,
+		Operation::Receive(_) => Key::Receive,
+		Operation::Function(ref function) => Key::Function(function.selector()),
+		Operation::Error(ref error) => Key::Error(error.selector()),
+		Operation::Event(ref event) => Key::Event(event.signature()),
+	}
+}
+
+/// Merges contracts into one, keeping all overloads of functions, events and errors and their documentation.
+pub fn merge<'a, I>(contracts: I, policy: MergePolicy) -> Result<Contract> where I: IntoIterator<Item = &'a Contract> {
+	let mut entries: Vec<Operation> = vec![];
+	let mut positions: HashMap<Key, usize> = HashMap::new();
+	let mut docs = BTreeMap::new();
+
+	for contract in contracts {
+		for operation in contract.operations() {
+			let key = key(operation);
+			match positions.get(&key).cloned() {
+				None => {
+					positions.insert(key, entries.len());
+					entries.push(operation.clone());
+				},
+				// identical entry is already merged
+				Some(position) if entries[position] == *operation => continue,
+				Some(position) => match policy {
+					MergePolicy::Strict => return Err(ErrorKind::MergeConflict(
+						human_readable::format_operation(&entries[position]),
+						human_readable::format_operation(operation),
+					).into()),
+					MergePolicy::KeepFirst => continue,
+					MergePolicy::KeepLast => entries[position] = operation.clone(),
+				},
+			}
+
+			if let Some(documentation) = contract.docs(&operation.entry()) {
+				docs.insert(operation.entry(), documentation.clone());
+			}
+		}
+	}
+
+	let mut result = Contract::default();
+	for operation in entries {
+		result.insert(operation);
+	}
+	result.docs = docs.into_iter().filter(|&(ref entry, _)| result.position(entry).is_some()).collect();
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use {Contract, ErrorKind};
+	use super::MergePolicy;
+
+	fn proxy() -> Contract {
+		Contract::from_human_readable(&[
+			"constructor(address implementation)",
+			"function upgradeTo(address implementation)",
+			"function burn(uint256 amount)",
+			"event Upgraded(address indexed implementation)",
+			"fallback() external payable",
+		]).unwrap()
+	}
+
+	fn implementation() -> Contract {
+		Contract::from_human_readable(&[
+			"function upgradeTo(address implementation)",
+			"function transfer(address to, uint256 value) returns (bool)",
+			"function transfer(address to, uint256 value, bytes data) returns (bool)",
+			"function collate_propagate_storage(bytes16)",
+			"event Upgraded(address indexed implementation)",
+			"error Unauthorized(address caller)",
+		]).unwrap()
+	}
+
+	#[test]
+	fn merge_contracts() {
+		let proxy = Contract::from_human_readable(&[
+			"function upgradeTo(address implementation)",
+			"event Upgraded(address indexed implementation)",
+			"receive() external payable",
+		]).unwrap();
+
+		let merged = Contract::merge(&[proxy, implementation()], MergePolicy::Strict).unwrap();
+		assert_eq!(merged.overloads("upgradeTo").count(), 1);
+		assert_eq!(merged.overloads("transfer").count(), 2);
+		assert_eq!(merged.functions().count(), 4);
+		assert_eq!(merged.events().count(), 1);
+		assert!(merged.error("Unauthorized").is_ok());
+		assert!(merged.receive().is_some());
+		assert!(merged.function_by_selector(hex!("42966c68")).is_ok());
+	}
+
+	#[test]
+	fn merge_conflicts() {
+		match *Contract::merge(&[proxy(), implementation()], MergePolicy::Strict).unwrap_err().kind() {
+			ErrorKind::MergeConflict(ref old, ref new) => {
+				assert_eq!(old, "function burn(uint256 amount)");
+				assert_eq!(new, "function collate_propagate_storage(bytes16)");
+			},
+			ref other => panic!("unexpected error: {:?}", other),
+		}
+
+		let merged = Contract::merge(&[proxy(), implementation()], MergePolicy::KeepFirst).unwrap();
+		assert_eq!(merged.function_by_selector(hex!("42966c68")).unwrap().name, "burn");
+		assert!(merged.function("collate_propagate_storage").is_err());
+		assert!(merged.constructor().is_some());
+
+		let merged = Contract::merge(&[proxy(), implementation()], MergePolicy::KeepLast).unwrap();
+		assert_eq!(merged.function_by_selector(hex!("42966c68")).unwrap().name, "collate_propagate_storage");
+		assert!(merged.function("burn").is_err());
+		assert_eq!(merged.functions().count(), 4);
+	}
+
+	#[test]
+	fn merge_conflicting_events() {
+		let first = Contract::from_human_readable(&["event Transfer(address indexed from, address indexed to, uint256 value)"]).unwrap();
+		let second = Contract::from_human_readable(&["event Transfer(address indexed from, address indexed to, uint256 indexed id)"]).unwrap();
+
+		assert!(Contract::merge(&[first.clone(), second.clone()], MergePolicy::Strict).is_err());
+		let merged = Contract::merge(&[first, second], MergePolicy::KeepLast).unwrap();
+		assert!(merged.event("Transfer").unwrap().inputs[2].indexed);
+	}
+
+	#[test]
+	fn merge_keeps_overloaded_events() {
+		let first = Contract::from_human_readable(&["event Transfer(address indexed from, address indexed to, uint256 value)"]).unwrap();
+		let second = Contract::from_human_readable(&[
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"event Transfer(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value)",
+		]).unwrap();
+
+		let merged = Contract::merge(&[first, second], MergePolicy::Strict).unwrap();
+		assert_eq!(merged.event_overloads("Transfer").count(), 2);
+		assert_eq!(merged.events().count(), 2);
+	}
+}
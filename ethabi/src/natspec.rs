@@ -0,0 +1,157 @@
+//! NatSpec documentation emitted by solc as `userdoc` and `devdoc`.
+
+use std::collections::BTreeMap;
+use serde_json::{self, Value};
+use {Entry, Result};
+
+/// NatSpec documentation of a constructor, function, event or custom error.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Documentation {
+	/// `@notice`, explanation for end users.
+	pub notice: Option<String>,
+	/// `@dev`, details for developers.
+	pub details: Option<String>,
+	/// `@param` descriptions by param name.
+	pub params: BTreeMap<String, String>,
+	/// `@return` descriptions by output name, or by `_0`, `_1`, etc. for unnamed outputs.
+	pub returns: BTreeMap<String, String>,
+}
+
+impl Documentation {
+	/// Returns description of the param with given name.
+	pub fn param(&self, name: &str) -> Option<&str> {
+		self.params.get(name).map(String::as_str)
+	}
+
+	/// Returns description of the output with given position and name.
+	pub fn output(&self, index: usize, name: &str) -> Option<&str> {
+		if name.is_empty() {
+			self.returns.get(&format!("_{}", index))
+		} else {
+			self.returns.get(name)
+		}.map(String::as_str)
+	}
+
+	/// Returns true if there is no documentation.
+	pub fn is_empty(&self) -> bool {
+		*self == Documentation::default()
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EntryDoc {
+	notice: Option<String>,
+	details: Option<String>,
+	#[serde(default)]
+	params: BTreeMap<String, String>,
+	#[serde(default)]
+	returns: BTreeMap<String, String>,
+}
+
+/// `userdoc` or `devdoc` object.
+#[derive(Debug, Default, Deserialize)]
+struct NatSpec {
+	#[serde(default)]
+	methods: BTreeMap<String, EntryDoc>,
+	#[serde(default)]
+	events: BTreeMap<String, EntryDoc>,
+	// the same error may be declared in several places, so solc writes a list
+	#[serde(default)]
+	errors: BTreeMap<String, Vec<EntryDoc>>,
+}
+
+impl NatSpec {
+	fn from_value(value: Value) -> Result<NatSpec> {
+		match value {
+			Value::Null => Ok(NatSpec::default()),
+			// solc < 0.8 writes natspec in combined json as a string
+			Value::String(ref s) => serde_json::from_str(s).map_err(From::from),
+			value => serde_json::from_value(value).map_err(From::from),
+		}
+	}
+
+	fn into_entries(self) -> Vec<(Entry, EntryDoc)> {
+		let methods = self.methods.into_iter().map(|(key, doc)| match key.as_str() {
+			"constructor" => (Entry::Constructor, doc),
+			_ => (Entry::Function(key), doc),
+		});
+		let events = self.events.into_iter().map(|(key, doc)| (Entry::Event(key), doc));
+		let errors = self.errors.into_iter()
+			.filter_map(|(key, docs)| docs.into_iter().next().map(|doc| (Entry::Error(key), doc)));
+		methods.chain(events).chain(errors).collect()
+	}
+}
+
+/// Reads documentation of abi entries from solc `userdoc` and `devdoc` output.
+pub(crate) fn parse(userdoc: Value, devdoc: Value) -> Result<BTreeMap<Entry, Documentation>> {
+	let mut result = BTreeMap::<Entry, Documentation>::new();
+
+	for (entry, doc) in NatSpec::from_value(userdoc)?.into_entries() {
+		result.entry(entry).or_default().notice = doc.notice;
+	}
+
+	for (entry, doc) in NatSpec::from_value(devdoc)?.into_entries() {
+		let documentation = result.entry(entry).or_default();
+		documentation.details = doc.details;
+		documentation.params = doc.params;
+		documentation.returns = doc.returns;
+	}
+
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use {Contract, Entry};
+
+	#[test]
+	fn load_docs() {
+		let mut contract = Contract::from_human_readable(&[
+			"function transfer(address to, uint256 value) returns (bool)",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"error InsufficientBalance(uint256 available)",
+		]).unwrap();
+
+		let userdoc = r#"{
+			"kind": "user",
+			"methods": {
+				"constructor": { "notice": "Creates the token" },
+				"transfer(address,uint256)": { "notice": "Moves tokens to `to`" }
+			},
+			"events": {
+				"Transfer(address,address,uint256)": { "notice": "Emitted on every transfer" }
+			},
+			"version": 1
+		}"#;
+		let devdoc = r#"{
+			"kind": "dev",
+			"methods": {
+				"transfer(address,uint256)": {
+					"details": "Fails if the balance is too low",
+					"params": { "to": "Recipient", "value": "Amount of tokens" },
+					"returns": { "_0": "Always true" }
+				}
+			},
+			"errors": {
+				"InsufficientBalance(uint256)": [{ "params": { "available": "Current balance" } }]
+			},
+			"title": "Token",
+			"version": 1
+		}"#;
+		contract.load_docs(userdoc.as_bytes(), devdoc.as_bytes()).unwrap();
+
+		let transfer = contract.docs(&Entry::Function("transfer(address,uint256)".to_owned())).unwrap();
+		assert_eq!(transfer.notice, Some("Moves tokens to `to`".to_owned()));
+		assert_eq!(transfer.details, Some("Fails if the balance is too low".to_owned()));
+		assert_eq!(transfer.param("value"), Some("Amount of tokens"));
+		assert_eq!(transfer.output(0, ""), Some("Always true"));
+
+		let event = contract.docs(&Entry::Event("Transfer(address,address,uint256)".to_owned())).unwrap();
+		assert_eq!(event.notice, Some("Emitted on every transfer".to_owned()));
+		assert_eq!(event.details, None);
+
+		let error = contract.docs(&Entry::Error("InsufficientBalance(uint256)".to_owned())).unwrap();
+		assert_eq!(error.param("available"), Some("Current balance"));
+		assert!(contract.docs(&Entry::Constructor).is_some());
+	}
+}
@@ -4,10 +4,11 @@ use serde::{Deserialize, Deserializer};
 use serde::de::{Error as SerdeError};
 use serde_json::Value;
 use serde_json::value::from_value;
-use {Function, Constructor, Event};
+use {AbiError, Entry, Function, Constructor, Event, Fallback, Receive};
 
 /// Operation type.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
 pub enum Operation {
 	/// Contract constructor.
 	Constructor(Constructor),
@@ -15,8 +16,26 @@ pub enum Operation {
 	Function(Function),
 	/// Contract event.
 	Event(Event),
-	/// Fallback, ignored.
-	Fallback,
+	/// Contract custom error.
+	Error(AbiError),
+	/// Contract fallback function.
+	Fallback(Fallback),
+	/// Contract receive function.
+	Receive(Receive),
+}
+
+impl Operation {
+	/// Returns the entry identifying this operation in a contract.
+	pub(crate) fn entry(&self) -> Entry {
+		match *self {
+			Operation::Constructor(_) => Entry::Constructor,
+			Operation::Function(ref function) => Entry::Function(function.signature()),
+			Operation::Event(ref event) => Entry::Event(event.signature_string()),
+			Operation::Error(ref error) => Entry::Error(error.signature()),
+			Operation::Fallback(_) => Entry::Fallback,
+			Operation::Receive(_) => Entry::Receive,
+		}
+	}
 }
 
 impl<'a> Deserialize<'a> for Operation {
@@ -46,7 +65,12 @@ impl<'a> Deserialize<'a> for Operation {
 				sanitize_name(&mut e.name);
 				Operation::Event(e)
 			}),
-			"fallback" => Ok(Operation::Fallback),
+			"error" => from_value(v).map(|mut e: AbiError| {
+				sanitize_name(&mut e.name);
+				Operation::Error(e)
+			}),
+			"fallback" => from_value(v).map(Operation::Fallback),
+			"receive" => from_value(v).map(Operation::Receive),
 			_ => Err(SerdeError::custom("Invalid operation type.")),
 		};
 		result.map_err(|e| D::Error::custom(e.to_string()))
@@ -57,7 +81,7 @@ impl<'a> Deserialize<'a> for Operation {
 mod tests {
 	use serde_json;
 	use super::Operation;
-	use {Function, Param, ParamType};
+	use {AbiError, Function, Param, ParamType, StateMutability};
 
 	#[test]
 	fn deserialize_operation() {
@@ -79,11 +103,39 @@ mod tests {
 				Param {
 					name: "a".to_owned(),
 					kind: ParamType::Address,
+					internal_type: None,
 					components: vec![]
 				}
 			],
 			outputs: vec![],
 			constant: false,
+			state_mutability: StateMutability::NonPayable,
+		}));
+	}
+
+	#[test]
+	fn deserialize_error() {
+		let s = r#"{
+			"type":"error",
+			"inputs": [{
+				"name":"available",
+				"type":"uint256"
+			}],
+			"name":"InsufficientBalance"
+		}"#;
+
+		let deserialized: Operation = serde_json::from_str(s).unwrap();
+
+		assert_eq!(deserialized, Operation::Error(AbiError {
+			name: "InsufficientBalance".to_owned(),
+			inputs: vec![
+				Param {
+					name: "available".to_owned(),
+					kind: ParamType::Uint(256),
+					internal_type: None,
+					components: vec![]
+				}
+			],
 		}));
 	}
 
@@ -186,15 +238,18 @@ mod tests {
 						ParamType::Uint(256),
 						ParamType::Uint(256),
 					]),
+					internal_type: None,
 					components: vec![
 						Param {
 							name: "start".to_owned(),
 							kind: ParamType::Uint(256),
+							internal_type: None,
 							components: vec![],
 						},
 						Param {
 							name: "end".to_owned(),
 							kind: ParamType::Uint(256),
+							internal_type: None,
 							components: vec![],
 						},
 					]
@@ -202,6 +257,7 @@ mod tests {
 			],
 			outputs: vec![],
 			constant: false,
+			state_mutability: StateMutability::NonPayable,
 		}));
 	}
 	
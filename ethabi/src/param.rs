@@ -1,5 +1,8 @@
 //! Function param.
-use ParamType;
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeStruct;
+use param_type::Writer;
+use {InternalType, ParamType};
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ParamIr {
@@ -8,6 +11,9 @@ pub struct ParamIr {
 	/// Param type.
 	#[serde(rename="type")]
 	pub kind: ParamType,
+	/// Solidity type, eg. `struct Market.Order`.
+	#[serde(default, rename="internalType")]
+	pub internal_type: Option<String>,
 	/// Components type for tuple.
 	#[serde(default)]
 	pub components: Vec<Param>,
@@ -21,82 +27,99 @@ pub struct Param {
 	pub name: String,
 	/// Param type.
 	pub kind: ParamType,
+	/// Solidity type, eg. `struct Market.Order`.
+	pub internal_type: Option<String>,
 	/// Components type for tuple.
 	pub components: Vec<Param>
 }
 
 impl Param {
+	/// Returns param type with tuples, including tuples nested in arrays, resolved from components.
 	pub fn true_type(&self) -> ParamType {
-		match self.kind {
-			ParamType::Address => self.kind.clone(),
-			ParamType::Bytes => self.kind.clone(),
-			ParamType::Int(_) => self.kind.clone(),
-			ParamType::Uint(_) => self.kind.clone(),
-			ParamType::Bool => self.kind.clone(),
-			ParamType::String => self.kind.clone(),
-			ParamType::FixedBytes(ref bytes) => self.kind.clone(),
-			ParamType::Array(ref tokens) => {
-			    let mut types = Box::new(ParamType::Bytes);
-			    let mut tuple_array = false;
-			    if self.kind == ParamType::Array(Box::new(ParamType::Tuple(vec![]))) {
-				tuple_array = true;
-			    }
-			    if tuple_array {
-				let mut temp_types = Vec::new();
-				for component in self.components.iter() {
-				    temp_types.push(component.true_type());
-				}
-				types = Box::new(ParamType::Tuple(temp_types));
-			    } else {
-				for component in self.components.iter() {
-				    types = Box::new(component.true_type());
-				}
-			    }
-			    ParamType::Array(types)
-			}
-			ParamType::FixedArray(ref tokens, size) => {
-			    let mut types = Box::new(ParamType::Bytes);
-			    let mut tuple_array = false;
-			    if self.kind == ParamType::FixedArray(Box::new(ParamType::Tuple(vec![])), size) {
-				tuple_array = true;
-			    }
-			    if tuple_array {
-				let mut temp_types = Vec::new();
-				for component in self.components.iter() {
-				    temp_types.push(component.true_type());
-				}
-				types = Box::new(ParamType::Tuple(temp_types));
-			    } else {
-				for component in self.components.iter() {
-				    types = Box::new(component.true_type());
-				}
-			    }
-			    ParamType::FixedArray(types, size)
-			}
-			ParamType::Tuple(ref tokens) => {
-			    let mut types = Vec::new();
-			    for component in self.components.iter() {
-				types.push(component.true_type());
-			    }
-			    ParamType::Tuple(types)
-			}
-		    }
+		resolve_tuple(&self.kind, &self.components)
+	}
+
+	/// Returns parsed solidity type of the param, if known.
+	pub fn parsed_internal_type(&self) -> Option<InternalType> {
+		self.internal_type.as_ref().map(|t| InternalType::parse(t))
 	}
 }
 
-impl From<ParamIr> for Param {
-	fn from(p: ParamIr) -> Self {
-		let kind = match p.kind {
-			ParamType::Tuple(_) if p.components.len() > 0  => {
-				let params: Vec<ParamType> = p.components.iter().map(|c| c.kind.clone()).collect();
-				ParamType::Tuple(params)
-			},
-			_ => p.kind,
+/// Replaces tuple in the given type, eg. `tuple[]`, with the types of components.
+pub fn resolve_tuple(kind: &ParamType, components: &[Param]) -> ParamType {
+	if components.is_empty() {
+		return kind.clone();
+	}
+
+	match *kind {
+		ParamType::Tuple(_) => ParamType::Tuple(components.iter().map(Param::true_type).collect()),
+		ParamType::Array(ref param) => ParamType::Array(Box::new(resolve_tuple(param, components))),
+		ParamType::FixedArray(ref param, len) => ParamType::FixedArray(Box::new(resolve_tuple(param, components)), len),
+		_ => kind.clone(),
+	}
+}
+
+/// Returns unnamed components of the tuple in the given type, eg. `(uint256,bool)[]`.
+///
+/// Used to write tuples of params created without components, which json abi can not describe otherwise.
+pub fn tuple_components(kind: &ParamType) -> Vec<Param> {
+	match *kind {
+		ParamType::Tuple(ref params) => params.iter()
+			.map(|kind| Param {
+				name: String::new(),
+				kind: kind.clone(),
+				internal_type: None,
+				components: tuple_components(kind),
+			})
+			.collect(),
+		ParamType::Array(ref param) | ParamType::FixedArray(ref param, _) => tuple_components(param),
+		_ => vec![],
+	}
+}
+
+/// Returns the type of param as written in json abi, where tuples described
+/// by `components` are written as `tuple`, eg. `tuple[]`.
+pub fn json_type(kind: &ParamType, has_components: bool) -> String {
+	match *kind {
+		ParamType::Tuple(ref params) if has_components || params.is_empty() => "tuple".to_owned(),
+		ParamType::Array(ref param) => format!("{}[]", json_type(param, has_components)),
+		ParamType::FixedArray(ref param, len) => format!("{}[{}]", json_type(param, has_components), len),
+		_ => Writer::write(kind),
+	}
+}
+
+impl Serialize for Param {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		let generated;
+		let components = if self.components.is_empty() {
+			generated = tuple_components(&self.kind);
+			&generated
+		} else {
+			&self.components
 		};
+		let has_components = !components.is_empty();
+		let mut param = serializer.serialize_struct("Param", 4)?;
+		param.serialize_field("name", &self.name)?;
+		param.serialize_field("type", &json_type(&self.kind, has_components))?;
+		match self.internal_type {
+			Some(ref internal_type) => param.serialize_field("internalType", internal_type)?,
+			None => param.skip_field("internalType")?,
+		}
+		if has_components {
+			param.serialize_field("components", components)?;
+		} else {
+			param.skip_field("components")?;
+		}
+		param.end()
+	}
+}
 
+impl From<ParamIr> for Param {
+	fn from(p: ParamIr) -> Self {
 		Param {
 			name: p.name,
-			kind: kind,
+			kind: resolve_tuple(&p.kind, &p.components),
+			internal_type: p.internal_type,
 			components: p.components
 		}
 	}
@@ -105,7 +128,7 @@ impl From<ParamIr> for Param {
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use {Param, ParamType};
+	use {InternalType, Param, ParamType};
 
 	#[test]
 	fn param_deserialization() {
@@ -119,10 +142,101 @@ mod tests {
 		assert_eq!(deserialized, Param {
 			name: "foo".to_owned(),
 			kind: ParamType::Address,
+			internal_type: None,
 			components: vec![]
 		});
 	}
 
+	#[test]
+	fn param_internal_type() {
+		let s = r#"{
+			"name": "order",
+			"type": "tuple",
+			"internalType": "struct Market.Order",
+			"components": [{ "name": "side", "type": "uint8", "internalType": "enum Market.Side" }]
+		}"#;
+
+		let deserialized: Param = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.internal_type, Some("struct Market.Order".to_owned()));
+		assert_eq!(deserialized.parsed_internal_type(), Some(InternalType::Struct {
+			contract: Some("Market".to_owned()),
+			name: "Order".to_owned(),
+		}));
+		assert_eq!(deserialized.components[0].parsed_internal_type(), Some(InternalType::Enum {
+			contract: Some("Market".to_owned()),
+			name: "Side".to_owned(),
+		}));
+
+		let serialized = serde_json::to_string(&deserialized).unwrap();
+		assert_eq!(serialized, r#"{"name":"order","type":"tuple","internalType":"struct Market.Order","components":[{"name":"side","type":"uint8","internalType":"enum Market.Side"}]}"#);
+	}
+
+	#[test]
+	fn param_true_type() {
+		let s = r#"[
+			{ "name": "a", "type": "uint256[2]" },
+			{ "name": "b", "type": "address[]" },
+			{
+				"name": "c",
+				"type": "tuple[]",
+				"components": [
+					{ "name": "d", "type": "bool" },
+					{
+						"name": "e",
+						"type": "tuple[2]",
+						"components": [{ "name": "f", "type": "bytes" }]
+					}
+				]
+			}
+		]"#;
+
+		let params: Vec<Param> = serde_json::from_str(s).unwrap();
+		let types: Vec<_> = params.iter().map(Param::true_type).collect();
+		assert_eq!(types, vec![
+			ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2),
+			ParamType::Array(Box::new(ParamType::Address)),
+			ParamType::Array(Box::new(ParamType::Tuple(vec![
+				ParamType::Bool,
+				ParamType::FixedArray(Box::new(ParamType::Tuple(vec![ParamType::Bytes])), 2),
+			]))),
+		]);
+	}
+
+	#[test]
+	fn param_serialization() {
+		let param = Param {
+			name: "foo".to_owned(),
+			kind: ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Uint(256)]))),
+			internal_type: None,
+			components: vec![
+				Param {
+					name: "bar".to_owned(),
+					kind: ParamType::Uint(256),
+					internal_type: None,
+					components: vec![]
+				}
+			]
+		};
+
+		let serialized = serde_json::to_string(&param).unwrap();
+		assert_eq!(serialized, r#"{"name":"foo","type":"tuple[]","components":[{"name":"bar","type":"uint256"}]}"#);
+		assert_eq!(serde_json::from_str::<Param>(&serialized).unwrap(), param);
+	}
+
+	#[test]
+	fn param_serialization_without_components() {
+		let param = Param {
+			name: "foo".to_owned(),
+			kind: ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Uint(256), ParamType::Tuple(vec![ParamType::Bool])]))),
+			internal_type: None,
+			components: vec![],
+		};
+
+		let serialized = serde_json::to_string(&param).unwrap();
+		assert_eq!(serialized, r#"{"name":"foo","type":"tuple[]","components":[{"name":"","type":"uint256"},{"name":"","type":"tuple","components":[{"name":"","type":"bool"}]}]}"#);
+		assert_eq!(serde_json::from_str::<Param>(&serialized).unwrap().kind, param.kind);
+	}
+
 	#[test]
 	fn param_deserialization_with_components() {
 		let s = r#"{
@@ -141,10 +255,12 @@ mod tests {
 		assert_eq!(deserialized, Param {
 			name: "foo".to_owned(),
 			kind: ParamType::Tuple(vec![ParamType::Uint(256)]),
+			internal_type: None,
 			components: vec![
 				Param {
 					name: "bar".to_owned(),
 					kind: ParamType::Uint(256),
+					internal_type: None,
 					components: vec![]
 				}
 			]
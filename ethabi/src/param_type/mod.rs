@@ -3,8 +3,11 @@
 mod deserialize;
 mod param_type;
 mod reader;
+mod serialize;
+mod solc;
 mod writer;
 
 pub use self::param_type::ParamType;
 pub use self::writer::Writer;
 pub use self::reader::Reader;
+pub use self::solc::SolcParamType;
@@ -1,7 +1,9 @@
 //! Function and event param types.
 
 use std::fmt;
-use super::Writer;
+use std::str::FromStr;
+use super::{Reader, Writer};
+use Error;
 
 /// Function and event param types.
 #[derive(Debug, Clone, PartialEq)]
@@ -26,6 +28,12 @@ pub enum ParamType {
 	FixedArray(Box<ParamType>, usize),
 	/// Tuple
 	Tuple(Vec<ParamType>),
+	/// Function pointer, ie. address followed by function selector.
+	Function,
+	/// Signed fixed-point number with given number of bits and decimals.
+	Fixed(usize, usize),
+	/// Unsigned fixed-point number with given number of bits and decimals.
+	UFixed(usize, usize),
 }
 
 impl fmt::Display for ParamType {
@@ -34,6 +42,14 @@ impl fmt::Display for ParamType {
 	}
 }
 
+impl FromStr for ParamType {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Reader::read(s)
+	}
+}
+
 impl ParamType {
     /// returns whether a zero length byte slice (`0x`) is
     /// a valid encoded form of this param type
@@ -55,6 +71,77 @@ impl ParamType {
 			_ => false,
 		}
 	}
+
+	/// Returns number of bytes taken by the type in the head of an encoding, ie. the static size
+	/// of static types and 32 bytes of the offset of dynamic types.
+	pub fn head_size(&self) -> usize {
+		self.static_size().unwrap_or(32)
+	}
+
+	/// Returns number of bytes taken by the encoding of a static type, or `None` for dynamic types.
+	pub fn static_size(&self) -> Option<usize> {
+		match *self {
+			ParamType::Bytes | ParamType::String | ParamType::Array(_) => None,
+			ParamType::FixedBytes(len) => Some(len.div_ceil(32) * 32),
+			ParamType::FixedArray(ref param, len) => param.static_size().map(|size| size * len),
+			ParamType::Tuple(ref params) => params.iter().map(ParamType::static_size).sum(),
+			_ => Some(32),
+		}
+	}
+
+	/// Returns the smallest number of bytes a valid encoding of given types can take,
+	/// eg. 64 bytes for `bytes`, which is encoded as offset and length of empty bytes.
+	pub fn min_encoded_size(types: &[ParamType]) -> usize {
+		types.iter().map(ParamType::min_size).sum()
+	}
+
+	fn min_size(&self) -> usize {
+		match *self {
+			ParamType::Bytes | ParamType::String | ParamType::Array(_) => 64,
+			ParamType::FixedArray(ref param, len) if param.is_dynamic() => 32 + param.min_size() * len,
+			ParamType::Tuple(ref params) if self.is_dynamic() => 32 + ParamType::min_encoded_size(params),
+			_ => self.head_size(),
+		}
+	}
+
+	/// Returns number of nested arrays and tuples, eg. 0 for `uint256` and 2 for `(uint256,bool)[]`.
+	pub fn nesting_depth(&self) -> usize {
+		match *self {
+			ParamType::Array(ref param) | ParamType::FixedArray(ref param, _) => 1 + param.nesting_depth(),
+			ParamType::Tuple(ref params) => 1 + params.iter().map(ParamType::nesting_depth).max().unwrap_or(0),
+			_ => 0,
+		}
+	}
+
+	/// Returns true if the type can be used in solidity, eg. it is not `uint7` or `bytes33`.
+	pub fn is_valid(&self) -> bool {
+		match *self {
+			ParamType::Int(len) | ParamType::Uint(len) => ParamType::is_valid_int_size(len),
+			ParamType::Fixed(len, decimals) | ParamType::UFixed(len, decimals) => {
+				ParamType::is_valid_int_size(len) && ParamType::is_valid_fixed_decimals(decimals)
+			},
+			ParamType::FixedBytes(len) => ParamType::is_valid_fixed_bytes_size(len),
+			ParamType::Array(ref param) => param.is_valid(),
+			ParamType::FixedArray(ref param, len) => len > 0 && param.is_valid(),
+			ParamType::Tuple(ref params) => params.iter().all(ParamType::is_valid),
+			_ => true,
+		}
+	}
+
+	/// Returns true if `int<len>` and `uint<len>` are valid types, ie. `len` is a multiple of 8 up to 256.
+	pub(crate) fn is_valid_int_size(len: usize) -> bool {
+		len > 0 && len <= 256 && len % 8 == 0
+	}
+
+	/// Returns true if `fixed<M>x<decimals>` and `ufixed<M>x<decimals>` are valid types, ie. `decimals` is at most 80.
+	pub(crate) fn is_valid_fixed_decimals(decimals: usize) -> bool {
+		decimals <= 80
+	}
+
+	/// Returns true if `bytes<len>` is a valid type, ie. `len` is between 1 and 32.
+	pub(crate) fn is_valid_fixed_bytes_size(len: usize) -> bool {
+		len > 0 && len <= 32
+	}
 }
 
 #[cfg(test)]
@@ -74,5 +161,51 @@ mod tests {
 		assert_eq!(format!("{}", ParamType::FixedArray(Box::new(ParamType::String), 2)), "string[2]".to_owned());
 		assert_eq!(format!("{}", ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 2)), "bool[][2]".to_owned());
 		assert_eq!(format!("{}", ParamType::Tuple(vec![ParamType::Bool, ParamType::Uint(256)])), "(bool,uint256)".to_owned());
+		assert_eq!(format!("{}", ParamType::Function), "function".to_owned());
+		assert_eq!(format!("{}", ParamType::Fixed(128, 18)), "fixed128x18".to_owned());
+	}
+
+	#[test]
+	fn test_param_type_from_str() {
+		let kind = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address, ParamType::FixedBytes(32)])));
+		assert_eq!("(address,bytes32)[]".parse::<ParamType>().unwrap(), kind);
+		assert_eq!(kind.to_string().parse::<ParamType>().unwrap(), kind);
+		assert!("tuple[]".parse::<ParamType>().is_err());
+
+		let empty = ParamType::Tuple(vec![]);
+		assert_eq!(empty.to_string(), "()");
+		assert_eq!(empty.to_string().parse::<ParamType>().unwrap(), empty);
+	}
+
+	#[test]
+	fn test_param_type_sizes() {
+		let dynamic_tuple = ParamType::Tuple(vec![ParamType::Uint(256), ParamType::String]);
+		let static_tuple = ParamType::Tuple(vec![ParamType::Address, ParamType::FixedArray(Box::new(ParamType::Bool), 3)]);
+
+		assert_eq!(ParamType::Uint(8).static_size(), Some(32));
+		assert_eq!(ParamType::FixedBytes(4).static_size(), Some(32));
+		assert_eq!(static_tuple.static_size(), Some(128));
+		assert_eq!(static_tuple.head_size(), 128);
+		assert_eq!(dynamic_tuple.static_size(), None);
+		assert_eq!(dynamic_tuple.head_size(), 32);
+		assert_eq!(ParamType::Array(Box::new(ParamType::Bool)).head_size(), 32);
+
+		assert_eq!(ParamType::min_encoded_size(&[]), 0);
+		assert_eq!(ParamType::min_encoded_size(&[ParamType::Bool, ParamType::Bytes]), 96);
+		assert_eq!(ParamType::min_encoded_size(&[dynamic_tuple.clone()]), 128);
+		assert_eq!(ParamType::min_encoded_size(&[ParamType::FixedArray(Box::new(ParamType::String), 2)]), 160);
+
+		assert_eq!(ParamType::Bool.nesting_depth(), 0);
+		assert_eq!(ParamType::Array(Box::new(dynamic_tuple)).nesting_depth(), 2);
+		assert_eq!(ParamType::Tuple(vec![]).nesting_depth(), 1);
+	}
+
+	#[test]
+	fn test_param_type_is_valid() {
+		assert!(ParamType::Fixed(128, 18).is_valid());
+		assert!(ParamType::UFixed(8, 0).is_valid());
+		assert!(!ParamType::Fixed(7, 18).is_valid());
+		assert!(!ParamType::UFixed(256, 81).is_valid());
+		assert!(ParamType::Function.is_valid());
 	}
 }
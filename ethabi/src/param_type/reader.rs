@@ -4,105 +4,227 @@ use {ParamType, Error, ErrorKind};
 pub struct Reader;
 
 impl Reader {
-	/// Converts string to param type.
+	/// Converts string to param type, eg. `(uint256,bytes32)[]`.
+	///
+	/// Whitespace around type names, brackets and commas is ignored.
 	pub fn read(name: &str) -> Result<ParamType, Error> {
-		// check if it is a fixed or dynamic array.
-		match name.chars().last() {
-			Some(']') => {
-				// take number part
-				let num: String = name.chars()
-					.rev()
-					.skip(1)
-					.take_while(|c| *c != '[')
-					.collect::<String>()
-					.chars()
-					.rev()
-					.collect();
-
-				let count = name.chars().count();
-				if num.is_empty() {
-					// we already know it's a dynamic array!
-					let subtype = try!(Reader::read(&name[..count - 2]));
-					return Ok(ParamType::Array(Box::new(subtype)));
-				} else {
-					// it's a fixed array.
-					let len = try!(usize::from_str_radix(&num, 10));
-					let subtype = try!(Reader::read(&name[..count - num.len() - 2]));
-					return Ok(ParamType::FixedArray(Box::new(subtype), len));
-				}
+		let mut parser = Parser::new(name);
+		let result = parser.param_type()?;
+		parser.end()?;
+		Ok(result)
+	}
+
+	/// Converts type of a json abi param to param type.
+	///
+	/// Json abi writes tuples as `tuple`, `tuple[]`, etc. and lists their members in `components`,
+	/// so these are read as empty tuples, to be filled by the caller.
+	pub(crate) fn read_json(name: &str) -> Result<ParamType, Error> {
+		let mut parser = Parser::new(name);
+		let result = if parser.keyword("tuple") {
+			parser.suffixes(ParamType::Tuple(vec![]))?
+		} else {
+			parser.param_type()?
+		};
+		parser.end()?;
+		Ok(result)
+	}
+
+	/// Reads array suffixes, eg. `[2][]`, at the start of `input` and applies them to `kind`.
+	///
+	/// Returns the resulting type together with the length of the suffixes read.
+	pub(crate) fn read_suffixes(input: &str, kind: ParamType) -> Result<(ParamType, usize), Error> {
+		let mut parser = Parser::new(input);
+		let result = parser.suffixes(kind)?;
+		Ok((result, parser.pos))
+	}
+}
+
+/// Recursive descent parser of type strings.
+struct Parser<'a> {
+	input: &'a str,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn new(input: &'a str) -> Self {
+		Parser { input, pos: 0 }
+	}
+
+	fn error<T>(&self, pos: usize, expected: &str) -> Result<T, Error> {
+		Err(ErrorKind::InvalidType(self.input.to_owned(), pos, expected.to_owned()).into())
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.input[self.pos..].chars().next()
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+			self.pos += c.len_utf8();
+		}
+	}
+
+	fn take_while<F>(&mut self, f: F) -> &'a str where F: Fn(char) -> bool {
+		let start = self.pos;
+		while let Some(c) = self.peek().filter(|c| f(*c)) {
+			self.pos += c.len_utf8();
+		}
+		&self.input[start..self.pos]
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		self.skip_whitespace();
+		if self.peek() == Some(c) {
+			self.pos += c.len_utf8();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn keyword(&mut self, keyword: &str) -> bool {
+		self.skip_whitespace();
+		let start = self.pos;
+		if self.take_while(|c| c.is_ascii_alphanumeric()) == keyword {
+			true
+		} else {
+			self.pos = start;
+			false
+		}
+	}
+
+	fn end(&mut self) -> Result<(), Error> {
+		self.skip_whitespace();
+		match self.peek() {
+			None => Ok(()),
+			Some(_) => self.error(self.pos, "end of type"),
+		}
+	}
+
+	fn param_type(&mut self) -> Result<ParamType, Error> {
+		self.skip_whitespace();
+		let kind = match self.peek() {
+			Some('(') => self.tuple()?,
+			_ => self.elementary()?,
+		};
+		self.suffixes(kind)
+	}
+
+	fn tuple(&mut self) -> Result<ParamType, Error> {
+		// skip `(`
+		self.pos += 1;
+		if self.eat(')') {
+			return Ok(ParamType::Tuple(vec![]));
+		}
+		let mut params = vec![self.param_type()?];
+		loop {
+			if self.eat(',') {
+				params.push(self.param_type()?);
+			} else if self.eat(')') {
+				return Ok(ParamType::Tuple(params));
+			} else {
+				return self.error(self.pos, "`,` or `)`");
 			}
-			Some(')') => {
-				if name.chars().next() == Some('(') {
-					let mut subtypes = Vec::new();
-					let mut nested = 0isize;
-					let mut last_item = 1;
-
-					for (pos, c) in name.chars().enumerate() {
-						match c {
-							'(' => {
-								nested += 1;
-							}
-							')' => {
-								nested -= 1;
-								if nested < 0 {
-									return Err(ErrorKind::InvalidName(name.to_owned()).into());
-								} else if nested == 0 {
-									let sub = &name[last_item..pos];
-									let subtype = Reader::read(sub)?;
-									subtypes.push(subtype);
-									last_item = pos + 1;
-								}
-							}
-							',' if nested == 1 => {
-								let sub = &name[last_item..pos];
-								let subtype = Reader::read(sub)?;
-								subtypes.push(subtype);
-								last_item = pos + 1;
-							}
-							_ => ()
-						}
-					}
-					return Ok(ParamType::Tuple(subtypes));
-				}
+		}
+	}
 
+	fn suffixes(&mut self, mut kind: ParamType) -> Result<ParamType, Error> {
+		while self.eat('[') {
+			self.skip_whitespace();
+			let pos = self.pos;
+			let size = self.take_while(|c| c.is_ascii_digit());
+			kind = if size.is_empty() {
+				ParamType::Array(Box::new(kind))
+			} else {
+				match number(size) {
+					Some(len) if len > 0 => ParamType::FixedArray(Box::new(kind), len),
+					_ => return self.error(pos, "non-zero array size"),
+				}
+			};
+			if !self.eat(']') {
+				return self.error(self.pos, "`]`");
 			}
-			_ => ()
 		}
+		Ok(kind)
+	}
+
+	fn elementary(&mut self) -> Result<ParamType, Error> {
+		let start = self.pos;
+		let name = self.take_while(|c| c.is_ascii_alphanumeric());
+		let split = name.find(|c: char| c.is_ascii_digit()).unwrap_or(name.len());
+		let (prefix, size) = name.split_at(split);
+		let pos = start + split;
 
-		let result = match name {
-			"address" => ParamType::Address,
-			"bytes" => ParamType::Bytes,
-			"bool" => ParamType::Bool,
-			"string" => ParamType::String,
-			"int" => ParamType::Int(256),
-			"uint" => ParamType::Uint(256),
-			"tuple"=> ParamType::Tuple(vec![]),
-			s if s.starts_with("int") => {
-				let len = try!(usize::from_str_radix(&s[3..], 10));
-				ParamType::Int(len)
+		let result = match (prefix, size) {
+			("address", "") => ParamType::Address,
+			("bytes", "") => ParamType::Bytes,
+			("bool", "") => ParamType::Bool,
+			("string", "") => ParamType::String,
+			("function", "") => ParamType::Function,
+			("int", "") => ParamType::Int(256),
+			("uint", "") => ParamType::Uint(256),
+			("fixed", "") => ParamType::Fixed(128, 18),
+			("ufixed", "") => ParamType::UFixed(128, 18),
+			("int", size) => ParamType::Int(self.int_size(size, pos)?),
+			("uint", size) => ParamType::Uint(self.int_size(size, pos)?),
+			("bytes", size) => match number(size).filter(|len| ParamType::is_valid_fixed_bytes_size(*len)) {
+				Some(len) => ParamType::FixedBytes(len),
+				None => return self.error(pos, "size between 1 and 32"),
 			},
-			s if s.starts_with("uint") => {
-				let len = try!(usize::from_str_radix(&s[4..], 10));
-				ParamType::Uint(len)
+			("fixed", size) => {
+				let (len, decimals) = self.fixed_size(size, pos)?;
+				ParamType::Fixed(len, decimals)
 			},
-			s if s.starts_with("bytes") => {
-				let len = try!(usize::from_str_radix(&s[5..], 10));
-				ParamType::FixedBytes(len)
+			("ufixed", size) => {
+				let (len, decimals) = self.fixed_size(size, pos)?;
+				ParamType::UFixed(len, decimals)
 			},
-			_ => {
-				return Err(ErrorKind::InvalidName(name.to_owned()).into());
-			}
+			_ => return self.error(start, "type"),
 		};
 
 		Ok(result)
 	}
+
+	fn int_size(&self, size: &str, pos: usize) -> Result<usize, Error> {
+		match number(size).filter(|len| ParamType::is_valid_int_size(*len)) {
+			Some(len) => Ok(len),
+			None => self.error(pos, "size between 8 and 256 in steps of 8"),
+		}
+	}
+
+	/// Reads `<M>x<N>` suffix of `fixed<M>x<N>` and `ufixed<M>x<N>` types.
+	fn fixed_size(&self, size: &str, pos: usize) -> Result<(usize, usize), Error> {
+		let mut parts = size.splitn(2, 'x');
+		let len = parts.next().and_then(number).filter(|len| ParamType::is_valid_int_size(*len));
+		let decimals = parts.next().and_then(number).filter(|decimals| ParamType::is_valid_fixed_decimals(*decimals));
+		match (len, decimals) {
+			(Some(len), Some(decimals)) => Ok((len, decimals)),
+			_ => self.error(pos, "size `<M>x<N>` with M between 8 and 256 in steps of 8 and N at most 80"),
+		}
+	}
+}
+
+/// Parses decimal number without leading zeros.
+fn number(s: &str) -> Option<usize> {
+	if s.len() > 1 && s.starts_with('0') {
+		None
+	} else {
+		s.parse().ok()
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use ParamType;
+	use {ErrorKind, ParamType};
 	use super::Reader;
 
+	fn error(name: &str) -> (usize, String) {
+		match *Reader::read(name).unwrap_err().kind() {
+			ErrorKind::InvalidType(_, position, ref expected) => (position, expected.clone()),
+			ref other => panic!("unexpected error: {:?}", other),
+		}
+	}
+
 	#[test]
 	fn test_read_param() {
 		assert_eq!(Reader::read("address").unwrap(), ParamType::Address);
@@ -114,6 +236,60 @@ mod tests {
 		assert_eq!(Reader::read("uint").unwrap(), ParamType::Uint(256));
 		assert_eq!(Reader::read("int32").unwrap(), ParamType::Int(32));
 		assert_eq!(Reader::read("uint32").unwrap(), ParamType::Uint(32));
+		assert_eq!(Reader::read("int8").unwrap(), ParamType::Int(8));
+		assert_eq!(Reader::read("bytes1").unwrap(), ParamType::FixedBytes(1));
+		assert_eq!(Reader::read("function").unwrap(), ParamType::Function);
+		assert_eq!(Reader::read("fixed").unwrap(), ParamType::Fixed(128, 18));
+		assert_eq!(Reader::read("ufixed").unwrap(), ParamType::UFixed(128, 18));
+		assert_eq!(Reader::read("fixed64x10").unwrap(), ParamType::Fixed(64, 10));
+		assert_eq!(Reader::read("ufixed256x80").unwrap(), ParamType::UFixed(256, 80));
+	}
+
+	#[test]
+	fn test_read_invalid_sizes() {
+		assert!(Reader::read("uint7").is_err());
+		assert!(Reader::read("uint0").is_err());
+		assert!(Reader::read("int264").is_err());
+		assert!(Reader::read("bytes0").is_err());
+		assert!(Reader::read("bytes33").is_err());
+		assert!(Reader::read("bytes33[]").is_err());
+		assert!(Reader::read("uint999").is_err());
+		assert!(Reader::read("uint08").is_err());
+		assert!(Reader::read("fixed128").is_err());
+		assert!(Reader::read("ufixed128x81").is_err());
+		assert!(Reader::read("bool[0]").is_err());
+	}
+
+	#[test]
+	fn test_read_malformed() {
+		assert_eq!(error("uint999"), (4, "size between 8 and 256 in steps of 8".to_owned()));
+		assert_eq!(error("bytes0"), (5, "size between 1 and 32".to_owned()));
+		assert_eq!(error("(()"), (3, "`,` or `)`".to_owned()));
+		assert_eq!(error("(,)"), (1, "type".to_owned()));
+		assert_eq!(error("(address,bool"), (13, "`,` or `)`".to_owned()));
+		assert_eq!(error("(address,)"), (9, "type".to_owned()));
+		assert_eq!(error("uint256[2"), (9, "`]`".to_owned()));
+		assert_eq!(error("bool[0]"), (5, "non-zero array size".to_owned()));
+		assert_eq!(error("uint256)"), (7, "end of type".to_owned()));
+		assert_eq!(error("tuple"), (0, "type".to_owned()));
+		assert_eq!(error("uint 256"), (5, "end of type".to_owned()));
+		assert_eq!(error(""), (0, "type".to_owned()));
+	}
+
+	#[test]
+	fn test_read_whitespace() {
+		assert_eq!(Reader::read(" ( address , bool [ 2 ] ) [] ").unwrap(), ParamType::Array(Box::new(ParamType::Tuple(vec![
+			ParamType::Address,
+			ParamType::FixedArray(Box::new(ParamType::Bool), 2),
+		]))));
+	}
+
+	#[test]
+	fn test_read_json() {
+		assert_eq!(Reader::read_json("tuple").unwrap(), ParamType::Tuple(vec![]));
+		assert_eq!(Reader::read_json("tuple[][2]").unwrap(), ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Tuple(vec![])))), 2));
+		assert_eq!(Reader::read_json("uint8[]").unwrap(), ParamType::Array(Box::new(ParamType::Uint(8))));
+		assert!(Reader::read_json("tuple2").is_err());
 	}
 
 	#[test]
@@ -135,6 +311,8 @@ mod tests {
 	fn test_read_tuple_param() {
 		assert_eq!(Reader::read("(address,bool)").unwrap(), ParamType::Tuple(vec![ParamType::Address, ParamType::Bool]));
 		assert_eq!(Reader::read("(bool[3],uint256)").unwrap(), ParamType::Tuple(vec![ParamType::FixedArray(Box::new(ParamType::Bool), 3), ParamType::Uint(256)]));
+		assert_eq!(Reader::read("()").unwrap(), ParamType::Tuple(vec![]));
+		assert_eq!(Reader::read("( )[]").unwrap(), ParamType::Array(Box::new(ParamType::Tuple(vec![]))));
 	}
 
 	#[test]
@@ -0,0 +1,31 @@
+use serde::{Serialize, Serializer};
+use super::{ParamType, Writer};
+
+impl Serialize for ParamType {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		serializer.serialize_str(&Writer::write(self))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ParamType;
+
+	#[test]
+	fn param_type_serialization() {
+		let types = vec![
+			ParamType::Address,
+			ParamType::FixedBytes(32),
+			ParamType::Uint(256),
+			ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 5),
+			ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Uint(256)]))),
+			ParamType::Tuple(vec![]),
+		];
+		let serialized = serde_json::to_string(&types).unwrap();
+		assert_eq!(serialized, r#"["address","bytes32","uint256","bool[][5]","(bool,uint256)[]","()"]"#);
+
+		let deserialized: Vec<ParamType> = serde_json::from_str(&serialized).unwrap();
+		assert_eq!(deserialized, types);
+	}
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::ser::SerializeStruct;
+use param::json_type;
+use super::ParamType;
+
+/// Param type in the form of solc json abi, eg. `{"type":"tuple[]","components":[{"type":"bool"}]}`.
+///
+/// Unlike `ParamType` itself, which is (de)serialized as a canonical type string, eg. `(bool)[]`,
+/// tuples are written as `tuple` and the types of their members are listed in `components`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolcParamType(pub ParamType);
+
+/// Returns members of the tuple, possibly nested in arrays, eg. `(bool,string)[2]`.
+fn tuple_components(kind: &ParamType) -> Option<&Vec<ParamType>> {
+	match *kind {
+		ParamType::Tuple(ref params) => Some(params),
+		ParamType::Array(ref param) | ParamType::FixedArray(ref param, _) => tuple_components(param),
+		_ => None,
+	}
+}
+
+/// Replaces tuple, possibly nested in arrays, with the given members.
+fn with_components(kind: ParamType, components: Vec<ParamType>) -> ParamType {
+	match kind {
+		ParamType::Tuple(_) => ParamType::Tuple(components),
+		ParamType::Array(param) => ParamType::Array(Box::new(with_components(*param, components))),
+		ParamType::FixedArray(param, len) => ParamType::FixedArray(Box::new(with_components(*param, components)), len),
+		kind => kind,
+	}
+}
+
+impl Serialize for SolcParamType {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		let components = tuple_components(&self.0)
+			.map(|params| params.iter().cloned().map(SolcParamType).collect::<Vec<_>>())
+			.unwrap_or_default();
+		let mut param = serializer.serialize_struct("SolcParamType", 2)?;
+		param.serialize_field("type", &json_type(&self.0, !components.is_empty()))?;
+		if components.is_empty() {
+			param.skip_field("components")?;
+		} else {
+			param.serialize_field("components", &components)?;
+		}
+		param.end()
+	}
+}
+
+#[derive(Deserialize)]
+struct SolcParamTypeIr {
+	#[serde(rename = "type")]
+	kind: ParamType,
+	#[serde(default)]
+	components: Vec<SolcParamType>,
+}
+
+impl<'a> Deserialize<'a> for SolcParamType {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+		let ir = SolcParamTypeIr::deserialize(deserializer)?;
+		let kind = if ir.components.is_empty() {
+			ir.kind
+		} else {
+			with_components(ir.kind, ir.components.into_iter().map(|c| c.0).collect())
+		};
+		Ok(SolcParamType(kind))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ParamType;
+	use super::SolcParamType;
+
+	#[test]
+	fn solc_param_type_serialization() {
+		let kind = ParamType::FixedArray(Box::new(ParamType::Tuple(vec![
+			ParamType::Address,
+			ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::String]))),
+		])), 2);
+
+		let serialized = serde_json::to_string(&SolcParamType(kind.clone())).unwrap();
+		assert_eq!(serialized, r#"{"type":"tuple[2]","components":[{"type":"address"},{"type":"tuple[]","components":[{"type":"bool"},{"type":"string"}]}]}"#);
+
+		let deserialized: SolcParamType = serde_json::from_str(&serialized).unwrap();
+		assert_eq!(deserialized.0, kind);
+
+		let deserialized: SolcParamType = serde_json::from_str(r#"{"type":"uint256[]"}"#).unwrap();
+		assert_eq!(deserialized.0, ParamType::Array(Box::new(ParamType::Uint(256))));
+	}
+}
@@ -17,6 +17,9 @@ impl Writer {
 			ParamType::FixedArray(ref param, len) => format!("{}[{}]", Writer::write(param), len),
 			ParamType::Array(ref param) => format!("{}[]", Writer::write(param)),
 			ParamType::Tuple(ref params) => format!("({})", params.iter().map(Writer::write).collect::<Vec<String>>().join(",")),
+			ParamType::Function => "function".to_owned(),
+			ParamType::Fixed(len, decimals) => format!("fixed{}x{}", len, decimals),
+			ParamType::UFixed(len, decimals) => format!("ufixed{}x{}", len, decimals),
 		}
 	}
 }
@@ -39,5 +42,8 @@ mod tests {
 		assert_eq!(Writer::write(&ParamType::FixedArray(Box::new(ParamType::String), 2)), "string[2]".to_owned());
 		assert_eq!(Writer::write(&ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 2)), "bool[][2]".to_owned());
 		assert_eq!(Writer::write(&ParamType::Tuple(vec![ParamType::Bool, ParamType::Uint(256)])), "(bool,uint256)".to_owned());
+		assert_eq!(Writer::write(&ParamType::Function), "function".to_owned());
+		assert_eq!(Writer::write(&ParamType::Fixed(128, 18)), "fixed128x18".to_owned());
+		assert_eq!(Writer::write(&ParamType::UFixed(64, 10)), "ufixed64x10".to_owned());
 	}
 }
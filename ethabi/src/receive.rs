@@ -0,0 +1,32 @@
+//! Contract receive function.
+
+use StateMutability;
+
+fn payable() -> StateMutability {
+	StateMutability::Payable
+}
+
+/// Contract receive function specification.
+///
+/// Executed for calls with empty calldata, eg. plain ether transfers. Always payable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Receive {
+	/// Receive state mutability.
+	#[serde(default = "payable", rename = "stateMutability")]
+	pub state_mutability: StateMutability,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use {Receive, StateMutability};
+
+	#[test]
+	fn receive_deserialization() {
+		let receive: Receive = serde_json::from_str(r#"{ "type": "receive", "stateMutability": "payable" }"#).unwrap();
+		assert_eq!(receive.state_mutability, StateMutability::Payable);
+
+		let receive: Receive = serde_json::from_str(r#"{ "type": "receive" }"#).unwrap();
+		assert_eq!(receive.state_mutability, StateMutability::Payable);
+	}
+}
@@ -0,0 +1,206 @@
+//! Standard revert reasons.
+
+use std::fmt;
+use {decode, Bytes, ParamType, Token, Uint};
+
+/// Selector of `Error(string)`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)`.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Panic codes emitted by the compiler, see `Panic(uint256)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PanicCode {
+	/// `0x00`, generic compiler inserted panic.
+	Generic,
+	/// `0x01`, failed `assert`.
+	Assert,
+	/// `0x11`, arithmetic overflow or underflow outside of an `unchecked` block.
+	ArithmeticOverflow,
+	/// `0x12`, division or modulo by zero.
+	DivisionByZero,
+	/// `0x21`, conversion of a value that is too big or negative to an enum.
+	InvalidEnumValue,
+	/// `0x22`, access to an incorrectly encoded storage byte array.
+	InvalidStorageByteArray,
+	/// `0x31`, `pop()` on an empty array.
+	EmptyArrayPop,
+	/// `0x32`, array or slice index out of bounds.
+	OutOfBounds,
+	/// `0x41`, too much memory allocated or array too large.
+	OutOfMemory,
+	/// `0x51`, call to a zero-initialized variable of internal function type.
+	ZeroInitializedFunction,
+	/// Code not known to this library.
+	Other(Uint),
+}
+
+impl From<Uint> for PanicCode {
+	fn from(code: Uint) -> Self {
+		if code > Uint::from(0xff) {
+			return PanicCode::Other(code);
+		}
+
+		match code.low_u32() {
+			0x00 => PanicCode::Generic,
+			0x01 => PanicCode::Assert,
+			0x11 => PanicCode::ArithmeticOverflow,
+			0x12 => PanicCode::DivisionByZero,
+			0x21 => PanicCode::InvalidEnumValue,
+			0x22 => PanicCode::InvalidStorageByteArray,
+			0x31 => PanicCode::EmptyArrayPop,
+			0x32 => PanicCode::OutOfBounds,
+			0x41 => PanicCode::OutOfMemory,
+			0x51 => PanicCode::ZeroInitializedFunction,
+			_ => PanicCode::Other(code),
+		}
+	}
+}
+
+impl PanicCode {
+	/// Returns numeric value of the panic code.
+	pub fn code(&self) -> Uint {
+		match *self {
+			PanicCode::Generic => 0x00.into(),
+			PanicCode::Assert => 0x01.into(),
+			PanicCode::ArithmeticOverflow => 0x11.into(),
+			PanicCode::DivisionByZero => 0x12.into(),
+			PanicCode::InvalidEnumValue => 0x21.into(),
+			PanicCode::InvalidStorageByteArray => 0x22.into(),
+			PanicCode::EmptyArrayPop => 0x31.into(),
+			PanicCode::OutOfBounds => 0x32.into(),
+			PanicCode::OutOfMemory => 0x41.into(),
+			PanicCode::ZeroInitializedFunction => 0x51.into(),
+			PanicCode::Other(code) => code,
+		}
+	}
+
+	fn description(&self) -> &'static str {
+		match *self {
+			PanicCode::Generic => "generic panic",
+			PanicCode::Assert => "assertion failed",
+			PanicCode::ArithmeticOverflow => "arithmetic overflow or underflow",
+			PanicCode::DivisionByZero => "division or modulo by zero",
+			PanicCode::InvalidEnumValue => "invalid enum value",
+			PanicCode::InvalidStorageByteArray => "incorrectly encoded storage byte array",
+			PanicCode::EmptyArrayPop => "pop on empty array",
+			PanicCode::OutOfBounds => "array index out of bounds",
+			PanicCode::OutOfMemory => "out of memory",
+			PanicCode::ZeroInitializedFunction => "call to zero-initialized function",
+			PanicCode::Other(_) => "unknown panic",
+		}
+	}
+}
+
+impl fmt::Display for PanicCode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (0x{:x})", self.description(), self.code())
+	}
+}
+
+/// Reason of a reverted call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Revert {
+	/// `Error(string)`, emitted by `revert("reason")` and `require(condition, "reason")`.
+	Error(String),
+	/// `Panic(uint256)`, emitted by failed assertions and runtime checks.
+	Panic(PanicCode),
+	/// Revert without any data, eg. `revert()` or `require(condition)`.
+	Empty,
+	/// Revert data that is not a standard revert reason, eg. a custom error.
+	Unknown(Bytes),
+}
+
+impl Revert {
+	/// Decodes `Error(string)` and `Panic(uint256)` revert data.
+	///
+	/// Returns `None` if data is not one of these standard encodings.
+	pub fn decode(data: &[u8]) -> Option<Revert> {
+		if data.len() < 4 {
+			return None;
+		}
+
+		let (selector, data) = data.split_at(4);
+		if selector == ERROR_SELECTOR {
+			match decode(&[ParamType::String], data).ok().and_then(|tokens| tokens.into_iter().next()) {
+				Some(Token::String(reason)) => Some(Revert::Error(reason)),
+				_ => None,
+			}
+		} else if selector == PANIC_SELECTOR {
+			match decode(&[ParamType::Uint(256)], data).ok().and_then(|tokens| tokens.into_iter().next()) {
+				Some(Token::Uint(code)) => Some(Revert::Panic(code.into())),
+				_ => None,
+			}
+		} else {
+			None
+		}
+	}
+
+	/// Converts revert data to a revert reason, falling back to `Empty` and `Unknown`.
+	pub fn from_data(data: &[u8]) -> Revert {
+		if data.is_empty() {
+			return Revert::Empty;
+		}
+
+		Revert::decode(data).unwrap_or_else(|| Revert::Unknown(data.to_vec()))
+	}
+}
+
+impl fmt::Display for Revert {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Revert::Error(ref reason) => write!(f, "execution reverted: {}", reason),
+			Revert::Panic(ref code) => write!(f, "panic: {}", code),
+			Revert::Empty => write!(f, "execution reverted"),
+			Revert::Unknown(ref data) => write!(f, "execution reverted with data: {}", Token::Bytes(data.clone())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Revert, PanicCode};
+
+	#[test]
+	fn decode_error_string() {
+		let data = hex!("
+			08c379a0
+			0000000000000000000000000000000000000000000000000000000000000020
+			000000000000000000000000000000000000000000000000000000000000000e
+			4e6f7420656e6f75676820457468000000000000000000000000000000000000
+		");
+		assert_eq!(Revert::decode(&data), Some(Revert::Error("Not enough Eth".to_owned())));
+	}
+
+	#[test]
+	fn decode_panic() {
+		let data = hex!("
+			4e487b71
+			0000000000000000000000000000000000000000000000000000000000000011
+		");
+		assert_eq!(Revert::decode(&data), Some(Revert::Panic(PanicCode::ArithmeticOverflow)));
+
+		let data = hex!("
+			4e487b71
+			0000000000000000000000000000000000000000000000000000000000000032
+		");
+		let revert = Revert::decode(&data).unwrap();
+		assert_eq!(revert, Revert::Panic(PanicCode::OutOfBounds));
+		assert_eq!(revert.to_string(), "panic: array index out of bounds (0x32)");
+
+		let data = hex!("
+			4e487b71
+			0000000000000000000000000000000000000000000000000000000000000099
+		");
+		assert_eq!(Revert::decode(&data), Some(Revert::Panic(PanicCode::Other(0x99.into()))));
+	}
+
+	#[test]
+	fn decode_non_standard_revert() {
+		assert_eq!(Revert::decode(&hex!("cf479181")), None);
+		assert_eq!(Revert::decode(&hex!("08c379a0")), None);
+		assert_eq!(Revert::from_data(&[]), Revert::Empty);
+		assert_eq!(Revert::from_data(&hex!("cf479181")), Revert::Unknown(hex!("cf479181").to_vec()));
+	}
+}
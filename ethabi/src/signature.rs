@@ -14,13 +14,18 @@ pub fn long_signature(name: &str, params: &[ParamType]) -> Hash {
 	result.into()
 }
 
-fn fill_signature(name: &str, params: &[ParamType], result: &mut [u8]) {
+/// Returns canonical signature, eg. `transfer(address,uint256)`.
+pub fn signature_string(name: &str, params: &[ParamType]) -> String {
 	let types = params.iter()
 		.map(Writer::write)
 		.collect::<Vec<String>>()
 		.join(",");
 
-	let data: Vec<u8> = From::from(format!("{}({})", name, types).as_str());
+	format!("{}({})", name, types)
+}
+
+fn fill_signature(name: &str, params: &[ParamType], result: &mut [u8]) {
+	let data: Vec<u8> = From::from(signature_string(name, params).as_str());
 
 	let mut sponge = Keccak::new_keccak256();
 	sponge.update(&data);
@@ -0,0 +1,79 @@
+//! Function state mutability.
+
+/// Whether a function reads or modifies the blockchain state and whether it accepts ether.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StateMutability {
+	/// Does not read nor modify the state.
+	#[serde(rename = "pure")]
+	Pure,
+	/// Reads, but does not modify the state.
+	#[serde(rename = "view")]
+	View,
+	/// Modifies the state, does not accept ether.
+	#[serde(rename = "nonpayable")]
+	NonPayable,
+	/// Modifies the state and accepts ether.
+	#[serde(rename = "payable")]
+	Payable,
+}
+
+impl Default for StateMutability {
+	fn default() -> Self {
+		StateMutability::NonPayable
+	}
+}
+
+impl StateMutability {
+	/// Derives state mutability from `constant` and `payable` fields used by solc < 0.5.
+	pub fn from_legacy(constant: bool, payable: bool) -> Self {
+		match (constant, payable) {
+			(true, _) => StateMutability::View,
+			(false, true) => StateMutability::Payable,
+			(false, false) => StateMutability::NonPayable,
+		}
+	}
+
+	/// Resolves state mutability of an abi entry, preferring `stateMutability` field
+	/// over the legacy `constant` and `payable` ones.
+	pub(crate) fn resolve(state_mutability: Option<StateMutability>, constant: Option<bool>, payable: Option<bool>) -> Self {
+		state_mutability.unwrap_or_else(|| StateMutability::from_legacy(constant.unwrap_or(false), payable.unwrap_or(false)))
+	}
+
+	/// Returns true if the function does not modify the state and can be executed with `eth_call`.
+	pub fn is_constant(&self) -> bool {
+		match *self {
+			StateMutability::Pure | StateMutability::View => true,
+			StateMutability::NonPayable | StateMutability::Payable => false,
+		}
+	}
+
+	/// Returns true if the function accepts ether.
+	pub fn is_payable(&self) -> bool {
+		*self == StateMutability::Payable
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use StateMutability;
+
+	#[test]
+	fn state_mutability_deserialization() {
+		let s = r#"["pure", "view", "nonpayable", "payable"]"#;
+		let deserialized: Vec<StateMutability> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![
+			StateMutability::Pure,
+			StateMutability::View,
+			StateMutability::NonPayable,
+			StateMutability::Payable,
+		]);
+	}
+
+	#[test]
+	fn state_mutability_from_legacy() {
+		assert_eq!(StateMutability::from_legacy(true, false), StateMutability::View);
+		assert_eq!(StateMutability::from_legacy(false, true), StateMutability::Payable);
+		assert_eq!(StateMutability::from_legacy(false, false), StateMutability::NonPayable);
+	}
+}
@@ -1,5 +1,6 @@
 use token::{Tokenizer, StrictTokenizer};
-use util::{pad_u32, pad_i32};
+use util::pad_u32;
+use I256;
 use errors::Error;
 
 /// Tries to parse string as a token. Does not require string to clearly represent the value.
@@ -36,13 +37,25 @@ impl Tokenizer for LenientTokenizer {
 		Ok(pad_u32(uint))
 	}
 
+	fn tokenize_function(value: &str) -> Result<[u8; 24], Error> {
+		StrictTokenizer::tokenize_function(value)
+	}
+
+	fn tokenize_fixed(value: &str, size: usize, decimals: usize) -> Result<[u8; 32], Error> {
+		StrictTokenizer::tokenize_fixed(value, size, decimals)
+	}
+
+	fn tokenize_ufixed(value: &str, size: usize, decimals: usize) -> Result<[u8; 32], Error> {
+		StrictTokenizer::tokenize_ufixed(value, size, decimals)
+	}
+
 	fn tokenize_int(value: &str) -> Result<[u8; 32], Error> {
 		let result = StrictTokenizer::tokenize_int(value);
 		if result.is_ok() {
 			return result;
 		}
 
-		let int = try!(i32::from_str_radix(value, 10));
-		Ok(pad_i32(int))
+		let int = I256::from_dec_str(value)?;
+		Ok(int.into_raw().into())
 	}
 }
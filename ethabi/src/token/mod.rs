@@ -1,11 +1,13 @@
 //! ABI param and parsing for it.
 
 mod lenient;
+mod named;
 mod strict;
 mod token;
 
-use {ParamType, Error, ErrorKind, ResultExt};
+use {ParamType, Error, ErrorKind, I256, ResultExt};
 pub use self::lenient::LenientTokenizer;
+pub use self::named::NamedToken;
 pub use self::strict::StrictTokenizer;
 pub use self::token::Token;
 
@@ -20,10 +22,23 @@ pub trait Tokenizer {
 			ParamType::Bytes => Self::tokenize_bytes(value).map(Token::Bytes),
 			ParamType::FixedBytes(len) => Self::tokenize_fixed_bytes(value, len).map(Token::FixedBytes),
 			ParamType::Uint(_) => Self::tokenize_uint(value).map(Into::into).map(Token::Uint),
-			ParamType::Int(_) => Self::tokenize_int(value).map(Into::into).map(Token::Int),
+			ParamType::Int(size) => Self::tokenize_int(value).map(Into::into).and_then(|int| if I256::from_raw(int).fits(size) {
+				Ok(Token::Int(int))
+			} else {
+				Err(ErrorKind::InvalidData.into())
+			}),
 			ParamType::Array(ref p) => Self::tokenize_array(value, p).map(Token::Array),
 			ParamType::FixedArray(ref p, len) => Self::tokenize_fixed_array(value, p, len).map(Token::FixedArray),
 			ParamType::Tuple(ref params) => Self::tokenize_tuple(value, params).map(Token::Tuple),
+			ParamType::Function => Self::tokenize_function(value).map(|function| {
+				let mut address = [0u8; 20];
+				address.copy_from_slice(&function[..20]);
+				let mut selector = [0u8; 4];
+				selector.copy_from_slice(&function[20..]);
+				Token::Function(address.into(), selector)
+			}),
+			ParamType::Fixed(size, decimals) => Self::tokenize_fixed(value, size, decimals).map(Into::into).map(Token::Fixed),
+			ParamType::UFixed(size, decimals) => Self::tokenize_ufixed(value, size, decimals).map(Into::into).map(Token::UFixed),
 		}.chain_err(|| format!("Cannot parse {}", param))
 	}
 
@@ -166,11 +181,21 @@ pub trait Tokenizer {
 
 	/// Tries to parse a value as signed integer.
 	fn tokenize_int(value: &str) -> Result<[u8; 32], Error>;
+
+	/// Tries to parse a value as function pointer, ie. address followed by function selector.
+	fn tokenize_function(value: &str) -> Result<[u8; 24], Error>;
+
+	/// Tries to parse a decimal number, eg. `-1.25`, as signed fixed-point number with given size and decimals.
+	fn tokenize_fixed(value: &str, size: usize, decimals: usize) -> Result<[u8; 32], Error>;
+
+	/// Tries to parse a decimal number, eg. `1.25`, as unsigned fixed-point number with given size and decimals.
+	fn tokenize_ufixed(value: &str, size: usize, decimals: usize) -> Result<[u8; 32], Error>;
 }
 
 #[cfg(test)]
 mod test {
-	use super::{LenientTokenizer, Tokenizer, ParamType};
+	use I256;
+	use super::{LenientTokenizer, Tokenizer, ParamType, Token};
 	#[test]
 	fn single_quoted_in_array_must_error() {
 		assert!(LenientTokenizer::tokenize_array("[1,\"0,false]", &ParamType::Bool).is_err());
@@ -179,4 +204,12 @@ mod test {
 		assert!(LenientTokenizer::tokenize_array("[1,\"0\",false]", &ParamType::Bool).is_err());
 		assert!(LenientTokenizer::tokenize_array("[1,0]", &ParamType::Bool).is_ok());
 	}
+
+	#[test]
+	fn tokenize_negative_int() {
+		assert_eq!(LenientTokenizer::tokenize(&ParamType::Int(256), "-2").unwrap(), Token::Int(I256::from(-2i32).into()));
+		assert_eq!(LenientTokenizer::tokenize(&ParamType::Int(8), "-128").unwrap(), Token::Int(I256::from(-128i32).into()));
+		assert!(LenientTokenizer::tokenize(&ParamType::Int(8), "128").is_err());
+		assert!(LenientTokenizer::tokenize(&ParamType::Int(8), "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00").is_err());
+	}
 }
@@ -0,0 +1,20 @@
+use {Param, Token};
+
+/// Decoded token together with the name of its param.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedToken {
+	/// Param name.
+	pub name: String,
+	/// Decoded value.
+	pub value: Token,
+}
+
+impl NamedToken {
+	/// Names decoded tokens after the params they were decoded with.
+	pub(crate) fn from_params(params: &[Param], tokens: Vec<Token>) -> Vec<NamedToken> {
+		params.iter()
+			.zip(tokens.into_iter())
+			.map(|(param, value)| NamedToken { name: param.name.clone(), value })
+			.collect()
+	}
+}
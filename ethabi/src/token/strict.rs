@@ -1,6 +1,8 @@
 use hex::FromHex;
 use token::Tokenizer;
 use errors::{Error, ErrorKind};
+use util::fits_uint;
+use {I256, Uint};
 
 /// Tries to parse string as a token. Require string to clearly represent the value.
 pub struct StrictTokenizer;
@@ -66,6 +68,59 @@ impl Tokenizer for StrictTokenizer {
 			false => Err(ErrorKind::InvalidData.into())
 		}
 	}
+
+	fn tokenize_function(value: &str) -> Result<[u8; 24], Error> {
+		let hex : Vec<u8> = value.from_hex()?;
+		if hex.len() == 24 {
+			let mut function = [0u8; 24];
+			function.copy_from_slice(&hex);
+			Ok(function)
+		} else {
+			Err(ErrorKind::InvalidData.into())
+		}
+	}
+
+	fn tokenize_fixed(value: &str, size: usize, decimals: usize) -> Result<[u8; 32], Error> {
+		tokenize_decimal(value, size, decimals, true)
+	}
+
+	fn tokenize_ufixed(value: &str, size: usize, decimals: usize) -> Result<[u8; 32], Error> {
+		tokenize_decimal(value, size, decimals, false)
+	}
+}
+
+/// Parses decimal number as an integer scaled by `10^decimals`, negative numbers in two's complement.
+///
+/// Fails if the number has more fractional digits than `decimals`, as it can not be represented exactly,
+/// or if the scaled number does not fit in `size` bits.
+fn tokenize_decimal(value: &str, size: usize, decimals: usize, signed: bool) -> Result<[u8; 32], Error> {
+	let (sign, value) = if signed && value.starts_with('-') {
+		("-", &value[1..])
+	} else {
+		("", value)
+	};
+
+	let mut parts = value.splitn(2, '.');
+	let integer = parts.next().unwrap_or("");
+	let fraction = parts.next().unwrap_or("");
+	let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+	if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) || fraction.len() > decimals {
+		return Err(ErrorKind::InvalidData.into());
+	}
+
+	let digits = format!("{}{}{}{}", sign, integer, fraction, "0".repeat(decimals - fraction.len()));
+	let (scaled, fits) = if signed {
+		let int = I256::from_dec_str(&digits)?;
+		(int.into_raw(), int.fits(size))
+	} else {
+		let uint = Uint::from_dec_str(&digits).map_err(|_| ErrorKind::InvalidData)?;
+		(uint, fits_uint(uint, size))
+	};
+
+	if !fits {
+		return Err(ErrorKind::InvalidData.into());
+	}
+	Ok(scaled.into())
 }
 
 #[cfg(test)]
@@ -157,5 +212,39 @@ mod tests {
 			])
 		);
 	}
-}
 
+	#[test]
+	fn tokenize_function() {
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Function, "1111111111111111111111111111111111111111a9059cbb").unwrap(),
+			Token::Function([0x11u8; 20].into(), [0xa9, 0x05, 0x9c, 0xbb])
+		);
+		assert!(StrictTokenizer::tokenize(&ParamType::Function, "1111111111111111111111111111111111111111").is_err());
+	}
+
+	#[test]
+	fn tokenize_fixed() {
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::UFixed(128, 2), "1.5").unwrap(), Token::UFixed(150.into()));
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::UFixed(128, 2), ".05").unwrap(), Token::UFixed(5.into()));
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Fixed(128, 18), "2").unwrap(), Token::Fixed(2_000_000_000_000_000_000u64.into()));
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Fixed(128, 1), "-0.1").unwrap(),
+			Token::Fixed(hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").into())
+		);
+		assert!(StrictTokenizer::tokenize(&ParamType::UFixed(128, 2), "-1.5").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::UFixed(128, 2), "1.505").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::Fixed(128, 2), ".").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::Fixed(128, 2), "1e5").is_err());
+	}
+
+	#[test]
+	fn tokenize_fixed_out_of_range() {
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::Fixed(8, 1), "12.7").unwrap(), Token::Fixed(127.into()));
+		assert!(StrictTokenizer::tokenize(&ParamType::Fixed(8, 1), "-12.8").is_ok());
+		assert!(StrictTokenizer::tokenize(&ParamType::Fixed(8, 1), "12.8").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::Fixed(8, 1), "-12.9").is_err());
+		assert!(StrictTokenizer::tokenize(&ParamType::Fixed(8, 1), "1000").is_err());
+		assert_eq!(StrictTokenizer::tokenize(&ParamType::UFixed(8, 0), "255").unwrap(), Token::UFixed(255.into()));
+		assert!(StrictTokenizer::tokenize(&ParamType::UFixed(8, 0), "256").is_err());
+	}
+}
@@ -2,7 +2,8 @@
 
 use hex::ToHex;
 use std::fmt;
-use {Address, Bytes, FixedBytes, ParamType, Uint};
+use util::fits_uint;
+use {Address, Bytes, FixedBytes, I256, ParamType, Uint};
 
 /// Ethereum ABI params.
 #[derive(Debug, PartialEq, Clone)]
@@ -27,6 +28,7 @@ pub enum Token {
 	/// Signed integer.
 	///
 	/// solidity name: int
+	/// Stored in two's complement, see `I256`.
 	Int(Uint),
 	/// Unisnged integer.
 	///
@@ -55,6 +57,22 @@ pub enum Token {
 	///
 	/// solidity name eg. (uint256,bool)
 	Tuple(Vec<Token>),
+	/// Function pointer.
+	///
+	/// solidity name: function
+	/// Encoded as address followed by function selector, right padded to 32 bytes.
+	Function(Address, [u8; 4]),
+	/// Signed fixed-point number, scaled by `10^decimals` of its type.
+	///
+	/// solidity name eg.: fixed128x18
+	/// Encoded in the same way as int. Displayed as the scaled integer, eg. `-15` for `-1.5` of `fixed8x1`,
+	/// see `Token::format_typed` for the decimal representation.
+	Fixed(Uint),
+	/// Unsigned fixed-point number, scaled by `10^decimals` of its type.
+	///
+	/// solidity name eg.: ufixed128x18
+	/// Encoded in the same way as uint. Displayed as the scaled integer, see `Token::format_typed`.
+	UFixed(Uint),
 }
 
 impl fmt::Display for Token {
@@ -66,7 +84,10 @@ impl fmt::Display for Token {
 			Token::Bytes(ref bytes) | Token::FixedBytes(ref bytes) => {
 				write!(f, "{}", bytes.to_hex::<String>())
 			}
-			Token::Uint(ref i) | Token::Int(ref i) => write!(f, "{:x}", i),
+			Token::Int(i) | Token::Fixed(i) => write!(f, "{}", I256::from_raw(i)),
+			Token::UFixed(ref i) => write!(f, "{}", i),
+			Token::Uint(ref i) => write!(f, "{:x}", i),
+			Token::Function(ref address, ref selector) => write!(f, "{:x}{}", address, selector.to_hex::<String>()),
 			Token::Array(ref arr) | Token::FixedArray(ref arr) => {
 				let s = arr
 					.iter()
@@ -94,13 +115,15 @@ impl Token {
 	///
 	/// Numeric types (`Int` and `Uint`) type check if the size of the token
 	/// type is of greater or equal size than the provided parameter type.
+	/// Signed integers also need to fit in `int<N>`, ie. be sign-extended from its size.
+	/// Fixed-point numbers need to fit in `M` bits of `fixed<M>x<N>` and `ufixed<M>x<N>`.
 	pub fn type_check(&self, param_type: &ParamType) -> bool {
 		match *self {
 			Token::Address(_) => *param_type == ParamType::Address,
 			Token::Bytes(_) => *param_type == ParamType::Bytes,
-			Token::Int(_) => {
-				if let ParamType::Int(_) = *param_type {
-					true
+			Token::Int(int) => {
+				if let ParamType::Int(size) = *param_type {
+					I256::from_raw(int).fits(size)
 				} else {
 					false
 				}
@@ -112,6 +135,21 @@ impl Token {
 					false
 				}
 			}
+			Token::Fixed(fixed) => {
+				if let ParamType::Fixed(size, _) = *param_type {
+					I256::from_raw(fixed).fits(size)
+				} else {
+					false
+				}
+			}
+			Token::UFixed(ufixed) => {
+				if let ParamType::UFixed(size, _) = *param_type {
+					fits_uint(ufixed, size)
+				} else {
+					false
+				}
+			}
+			Token::Function(_, _) => *param_type == ParamType::Function,
 			Token::Bool(_) => *param_type == ParamType::Bool,
 			Token::String(_) => *param_type == ParamType::String,
 			Token::FixedBytes(ref bytes) => {
@@ -181,6 +219,11 @@ impl Token {
 		}
 	}
 
+	/// Converts token to...
+	pub fn to_i256(self) -> Option<I256> {
+		self.to_int().map(I256::from_raw)
+	}
+
 	/// Converts token to...
 	pub fn to_uint(self) -> Option<Uint> {
 		match self {
@@ -189,6 +232,30 @@ impl Token {
 		}
 	}
 
+	/// Converts token to...
+	pub fn to_fixed(self) -> Option<Uint> {
+		match self {
+			Token::Fixed(fixed) => Some(fixed),
+			_ => None,
+		}
+	}
+
+	/// Converts token to...
+	pub fn to_ufixed(self) -> Option<Uint> {
+		match self {
+			Token::UFixed(ufixed) => Some(ufixed),
+			_ => None,
+		}
+	}
+
+	/// Converts token to...
+	pub fn to_function(self) -> Option<(Address, [u8; 4])> {
+		match self {
+			Token::Function(address, selector) => Some((address, selector)),
+			_ => None,
+		}
+	}
+
 	/// Converts token to...
 	pub fn to_bool(self) -> Option<bool> {
 		match self {
@@ -239,6 +306,44 @@ impl Token {
 		}
 	}
 
+	/// Formats the token like `Display`, but uses the decimals of fixed-point types
+	/// to print `Fixed` and `UFixed` as decimal numbers, eg. `-1.5` for `fixed8x1`.
+	///
+	/// Falls back to `Display` for tokens which do not match the parameter type.
+	pub fn format_typed(&self, param_type: &ParamType) -> String {
+		let join = |tokens: &[Token], kind: &ParamType| tokens
+			.iter()
+			.map(|t| t.format_typed(kind))
+			.collect::<Vec<String>>()
+			.join(",");
+
+		match (self, param_type) {
+			(&Token::Fixed(fixed), &ParamType::Fixed(_, decimals)) => {
+				let value = I256::from_raw(fixed);
+				let sign = if value.is_negative() { "-" } else { "" };
+				format!("{}{}", sign, format_decimal(value.abs().to_string(), decimals))
+			}
+			(&Token::UFixed(ufixed), &ParamType::UFixed(_, decimals)) => {
+				format_decimal(ufixed.to_string(), decimals)
+			}
+			(&Token::Array(ref tokens), &ParamType::Array(ref kind)) |
+			(&Token::FixedArray(ref tokens), &ParamType::FixedArray(ref kind, _)) => {
+				format!("[{}]", join(tokens, kind))
+			}
+			(&Token::Tuple(ref tokens), &ParamType::Tuple(ref kinds)) if tokens.len() == kinds.len() => {
+				let s = tokens
+					.iter()
+					.zip(kinds)
+					.map(|(t, kind)| t.format_typed(kind))
+					.collect::<Vec<String>>()
+					.join(",");
+
+				format!("({})", s)
+			}
+			_ => format!("{}", self),
+		}
+	}
+
 	/// Check if the Token is dynamic type.
 	pub fn is_dynamic(&self) -> bool {
 		match self {
@@ -251,10 +356,27 @@ impl Token {
 	}
 }
 
+/// Inserts the decimal point `decimals` digits from the right of `digits`,
+/// trimming trailing zeros of the fraction.
+fn format_decimal(digits: String, decimals: usize) -> String {
+	let digits = if digits.len() <= decimals {
+		format!("{}{}", "0".repeat(decimals + 1 - digits.len()), digits)
+	} else {
+		digits
+	};
+
+	let (integer, fraction) = digits.split_at(digits.len() - decimals);
+	let fraction = fraction.trim_end_matches('0');
+	if fraction.is_empty() {
+		integer.to_owned()
+	} else {
+		format!("{}.{}", integer, fraction)
+	}
+}
 
 #[cfg(test)]
 mod tests {
-	use {ParamType, Token};
+	use {I256, ParamType, Token};
 
 	#[test]
 	fn test_type_check() {
@@ -384,4 +506,41 @@ mod tests {
 			Token::Bool(true),
 		])));
 	}
+
+	#[test]
+	fn test_display_fixed() {
+		assert_eq!(format!("{}", Token::Fixed(I256::from(-15i32).into_raw())), "-15");
+		assert_eq!(format!("{}", Token::UFixed(150.into())), "150");
+	}
+
+	#[test]
+	fn test_format_typed_fixed() {
+		let fixed = Token::Fixed(I256::from(-15i32).into_raw());
+		assert_eq!(fixed.format_typed(&ParamType::Fixed(8, 1)), "-1.5");
+		assert_eq!(fixed.format_typed(&ParamType::Fixed(16, 3)), "-0.015");
+		assert_eq!(Token::UFixed(150.into()).format_typed(&ParamType::UFixed(16, 2)), "1.5");
+		assert_eq!(Token::UFixed(100.into()).format_typed(&ParamType::UFixed(16, 2)), "1");
+		assert_eq!(Token::UFixed(0.into()).format_typed(&ParamType::UFixed(16, 2)), "0");
+		assert_eq!(Token::UFixed(7.into()).format_typed(&ParamType::UFixed(16, 0)), "7");
+		assert_eq!(
+			Token::Array(vec![fixed.clone(), Token::Fixed(20.into())])
+				.format_typed(&ParamType::Array(Box::new(ParamType::Fixed(8, 1)))),
+			"[-1.5,2]"
+		);
+		assert_eq!(
+			Token::Tuple(vec![Token::Bool(true), fixed.clone()])
+				.format_typed(&ParamType::Tuple(vec![ParamType::Bool, ParamType::Fixed(8, 1)])),
+			"(true,-1.5)"
+		);
+		assert_eq!(fixed.format_typed(&ParamType::Bool), "-15");
+	}
+
+	#[test]
+	fn test_type_check_fixed_range() {
+		assert!(Token::Fixed(I256::from(-128i32).into_raw()).type_check(&ParamType::Fixed(8, 1)));
+		assert!(!Token::Fixed(I256::from(-129i32).into_raw()).type_check(&ParamType::Fixed(8, 1)));
+		assert!(!Token::Fixed(128.into()).type_check(&ParamType::Fixed(8, 1)));
+		assert!(Token::UFixed(255.into()).type_check(&ParamType::UFixed(8, 1)));
+		assert!(!Token::UFixed(256.into()).type_check(&ParamType::UFixed(8, 1)));
+	}
 }
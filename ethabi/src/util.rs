@@ -1,6 +1,6 @@
 //! Utils used by different modules.
 
-use {Error, ErrorKind};
+use {Error, ErrorKind, Uint};
 
 /// Convers vector of bytes with len equal n * 32, to a vector of slices.
 pub fn slice_data(data: &[u8]) -> Result<Vec<[u8; 32]>, Error> {
@@ -29,29 +29,7 @@ pub fn pad_u32(value: u32) -> [u8; 32] {
 	padded
 }
 
-/// Converts i32 to right aligned array of 32 bytes.
-pub fn pad_i32(value: i32) -> [u8; 32] {
-	if value >= 0 {
-		return pad_u32(value as u32);
-	}
-
-	let mut padded = [0xffu8; 32];
-	padded[28] = (value >> 24) as u8;
-	padded[29] = (value >> 16) as u8;
-	padded[30] = (value >> 8) as u8;
-	padded[31] = value as u8;
-	padded
-}
-
-#[cfg(test)]
-mod tests {
-	use super::pad_i32;
-
-	#[test]
-	fn test_i32() {
-		assert_eq!(hex!("0000000000000000000000000000000000000000000000000000000000000000").to_vec(), pad_i32(0).to_vec());
-		assert_eq!(hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").to_vec(), pad_i32(-1).to_vec());
-		assert_eq!(hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe").to_vec(), pad_i32(-2).to_vec());
-		assert_eq!(hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00").to_vec(), pad_i32(-256).to_vec());
-	}
+/// Returns true if unsigned integer fits in `bits`, ie. all higher bits are zero.
+pub fn fits_uint(value: Uint, bits: usize) -> bool {
+	bits >= 256 || value >> bits == Uint::zero()
 }
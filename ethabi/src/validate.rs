@@ -0,0 +1,284 @@
+//! Problems in abi that make it unusable.
+
+use std::collections::HashMap;
+use std::fmt;
+use hex::ToHex;
+use operation::Operation;
+use {Contract, ParamType, Param};
+
+/// Problem found in abi.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+	/// Type that can not be used in solidity, eg. `uint7` or `bytes33`.
+	InvalidType(ParamType),
+	/// Tuple param without components.
+	TupleWithoutComponents,
+	/// Function or error without name.
+	EmptyName,
+	/// Event with more indexed params than available topics, given as count and limit.
+	/// The limit is 3 for non-anonymous events and 4 for anonymous events.
+	TooManyIndexedParams(usize, usize),
+	/// Function has the same selector as another function, or error as another error,
+	/// given by its path.
+	DuplicateSelector([u8; 4], String),
+}
+
+impl fmt::Display for DiagnosticKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			DiagnosticKind::InvalidType(ref kind) => write!(f, "invalid type `{}`", kind),
+			DiagnosticKind::TupleWithoutComponents => write!(f, "tuple without components"),
+			DiagnosticKind::EmptyName => write!(f, "empty name"),
+			DiagnosticKind::TooManyIndexedParams(count, limit) => {
+				write!(f, "{} indexed params, at most {} are allowed", count, limit)
+			},
+			DiagnosticKind::DuplicateSelector(ref selector, ref other) => {
+				write!(f, "selector 0x{} is also used by `{}`", selector.to_hex::<String>(), other)
+			},
+		}
+	}
+}
+
+/// Problem found in abi, together with the json path to the offending entry or param,
+/// eg. `$[3].inputs[1]`.
+///
+/// The path refers to the normalized interface, ie. the index is a position among the entries
+/// of the loaded contract in declaration order, the order of `Contract::to_human_readable`.
+/// It matches the source json unless the json contains entries which are replaced on load,
+/// eg. a second constructor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+	/// Json path to the offending entry or param in the normalized interface.
+	pub path: String,
+	/// The problem.
+	pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {}", self.path, self.kind)
+	}
+}
+
+/// Returns all problems found in the contract, in declaration order of the offending entries.
+pub fn validate(contract: &Contract) -> Vec<Diagnostic> {
+	let mut result = vec![];
+	// functions and errors are selected in different contexts, so their selectors may clash
+	let mut function_selectors = HashMap::new();
+	let mut error_selectors = HashMap::new();
+
+	for (index, operation) in contract.operations().enumerate() {
+		let path = format!("$[{}]", index);
+		match *operation {
+			Operation::Constructor(ref constructor) => {
+				validate_params(&path, "inputs", &constructor.inputs, &mut result);
+			},
+			Operation::Function(ref function) => {
+				if function.name.is_empty() {
+					result.push(Diagnostic { path: path.clone(), kind: DiagnosticKind::EmptyName });
+				}
+				validate_selector(function.selector(), &path, &mut function_selectors, &mut result);
+				validate_params(&path, "inputs", &function.inputs, &mut result);
+				validate_params(&path, "outputs", &function.outputs, &mut result);
+			},
+			Operation::Error(ref error) => {
+				if error.name.is_empty() {
+					result.push(Diagnostic { path: path.clone(), kind: DiagnosticKind::EmptyName });
+				}
+				validate_selector(error.selector(), &path, &mut error_selectors, &mut result);
+				validate_params(&path, "inputs", &error.inputs, &mut result);
+			},
+			Operation::Event(ref event) => {
+				let indexed = event.inputs.iter().filter(|p| p.indexed).count();
+				let limit = if event.anonymous { 4 } else { 3 };
+				if indexed > limit {
+					result.push(Diagnostic { path: path.clone(), kind: DiagnosticKind::TooManyIndexedParams(indexed, limit) });
+				}
+				for (index, param) in event.inputs.iter().enumerate() {
+					validate_param(format!("{}.inputs[{}]", path, index), &param.kind, &param.components, &mut result);
+				}
+			},
+			Operation::Fallback(_) | Operation::Receive(_) => (),
+		}
+	}
+
+	result
+}
+
+fn validate_selector(selector: [u8; 4], path: &str, selectors: &mut HashMap<[u8; 4], String>, result: &mut Vec<Diagnostic>) {
+	match selectors.get(&selector) {
+		Some(other) => {
+			result.push(Diagnostic { path: path.to_owned(), kind: DiagnosticKind::DuplicateSelector(selector, other.clone()) });
+			return;
+		},
+		None => (),
+	}
+	selectors.insert(selector, path.to_owned());
+}
+
+fn validate_params(path: &str, field: &str, params: &[Param], result: &mut Vec<Diagnostic>) {
+	for (index, param) in params.iter().enumerate() {
+		validate_param(format!("{}.{}[{}]", path, field, index), &param.kind, &param.components, result);
+	}
+}
+
+fn validate_param(path: String, kind: &ParamType, components: &[Param], result: &mut Vec<Diagnostic>) {
+	if !kind.is_valid() {
+		result.push(Diagnostic { path: path.clone(), kind: DiagnosticKind::InvalidType(kind.clone()) });
+	}
+
+	if is_empty_tuple(kind) && components.is_empty() {
+		result.push(Diagnostic { path: path.clone(), kind: DiagnosticKind::TupleWithoutComponents });
+	}
+
+	validate_params(&path, "components", components, result);
+}
+
+fn is_empty_tuple(kind: &ParamType) -> bool {
+	match *kind {
+		ParamType::Tuple(ref params) => params.is_empty(),
+		ParamType::Array(ref param) | ParamType::FixedArray(ref param, _) => is_empty_tuple(param),
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {Contract, Entry, Param, ParamType};
+	use super::{Diagnostic, DiagnosticKind};
+
+	#[test]
+	fn validate_contract() {
+		let s = r#"[
+			{
+				"type": "function",
+				"name": "",
+				"inputs": [{ "name": "a", "type": "tuple" }],
+				"outputs": []
+			},
+			{
+				"type": "event",
+				"name": "Crowded",
+				"inputs": [
+					{ "name": "a", "type": "uint256", "indexed": true },
+					{ "name": "b", "type": "uint256", "indexed": true },
+					{ "name": "c", "type": "uint256", "indexed": true },
+					{ "name": "d", "type": "uint256", "indexed": true }
+				],
+				"anonymous": false
+			},
+			{
+				"type": "function",
+				"name": "collate_propagate_storage",
+				"inputs": [{ "name": "", "type": "bytes16" }],
+				"outputs": []
+			},
+			{
+				"type": "function",
+				"name": "burn",
+				"inputs": [{ "name": "", "type": "uint256" }],
+				"outputs": []
+			}
+		]"#;
+		let mut contract = Contract::load(s.as_bytes()).unwrap();
+		let mut burn = contract.function("burn").unwrap().clone();
+		burn.outputs.push(Param {
+			name: "".to_owned(),
+			kind: ParamType::Uint(7),
+			internal_type: None,
+			components: vec![],
+		});
+		contract.retain(|entry| *entry != Entry::Function(burn.signature()));
+		contract.add_function(burn);
+
+		let diagnostics = contract.validate();
+		assert_eq!(diagnostics, vec![
+			Diagnostic {
+				path: "$[0]".to_owned(),
+				kind: DiagnosticKind::EmptyName,
+			},
+			Diagnostic {
+				path: "$[0].inputs[0]".to_owned(),
+				kind: DiagnosticKind::TupleWithoutComponents,
+			},
+			Diagnostic {
+				path: "$[1]".to_owned(),
+				kind: DiagnosticKind::TooManyIndexedParams(4, 3),
+			},
+			Diagnostic {
+				path: "$[3]".to_owned(),
+				kind: DiagnosticKind::DuplicateSelector(hex!("42966c68"), "$[2]".to_owned()),
+			},
+			Diagnostic {
+				path: "$[3].outputs[0]".to_owned(),
+				kind: DiagnosticKind::InvalidType(ParamType::Uint(7)),
+			},
+		]);
+		assert_eq!(diagnostics[4].to_string(), "$[3].outputs[0]: invalid type `uint7`");
+	}
+
+	#[test]
+	fn validate_anonymous_event() {
+		let s = r#"[
+			{
+				"type": "event",
+				"name": "Four",
+				"inputs": [
+					{ "name": "a", "type": "uint256", "indexed": true },
+					{ "name": "b", "type": "uint256", "indexed": true },
+					{ "name": "c", "type": "uint256", "indexed": true },
+					{ "name": "d", "type": "uint256", "indexed": true }
+				],
+				"anonymous": true
+			},
+			{
+				"type": "event",
+				"name": "Five",
+				"inputs": [
+					{ "name": "a", "type": "uint256", "indexed": true },
+					{ "name": "b", "type": "uint256", "indexed": true },
+					{ "name": "c", "type": "uint256", "indexed": true },
+					{ "name": "d", "type": "uint256", "indexed": true },
+					{ "name": "e", "type": "uint256", "indexed": true }
+				],
+				"anonymous": true
+			}
+		]"#;
+		let contract = Contract::load(s.as_bytes()).unwrap();
+
+		let diagnostics = contract.validate();
+		assert_eq!(diagnostics, vec![
+			Diagnostic {
+				path: "$[1]".to_owned(),
+				kind: DiagnosticKind::TooManyIndexedParams(5, 4),
+			},
+		]);
+		assert_eq!(diagnostics[0].to_string(), "$[1]: 5 indexed params, at most 4 are allowed");
+	}
+
+	#[test]
+	fn function_and_error_selectors_do_not_clash() {
+		// both have selector 0x42966c68
+		let s = r#"[
+			{
+				"type": "function",
+				"name": "burn",
+				"inputs": [{ "name": "", "type": "uint256" }],
+				"outputs": []
+			},
+			{
+				"type": "error",
+				"name": "burn",
+				"inputs": [{ "name": "", "type": "uint256" }]
+			}
+		]"#;
+		let contract = Contract::load(s.as_bytes()).unwrap();
+		assert_eq!(contract.validate(), vec![]);
+	}
+
+	#[test]
+	fn load_rejects_invalid_types() {
+		let s = r#"[{ "type": "function", "name": "foo", "inputs": [{ "name": "a", "type": "bytes33" }], "outputs": [] }]"#;
+		assert!(Contract::load(s.as_bytes()).is_err());
+	}
+}
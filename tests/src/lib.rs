@@ -20,6 +20,10 @@ use_contract!(validators, "../res/Validators.abi");
 use_contract!(operations, "../res/Operations.abi");
 use_contract!(urlhint, "../res/urlhint.abi");
 use_contract!(test_rust_keywords, "../res/test_rust_keywords.abi");
+use_contract!(market, "../res/market.abi");
+use_contract!(test_artifact, "../res/test.artifact.json");
+use_contract!(truffle_artifact, "../res/truffle.artifact.json");
+use_contract!(combined_token, "../res/combined.json", "Token");
 
 #[cfg(test)]
 mod tests {
@@ -124,4 +128,43 @@ mod tests {
 		let wildcard_filter_sugared = eip20::events::transfer::wildcard_filter();
 		assert_eq!(wildcard_filter, wildcard_filter_sugared);
 	}
+
+	#[test]
+	fn encoding_tuple_input_works() {
+		use market;
+
+		let maker: Address = [1u8; 20].into();
+		let order = (maker, Uint::from(5), vec![0xabu8]);
+		let encoded = market::functions::place::encode_input(order.clone());
+		let expected = "61c917ea\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			0000000000000000000000000101010101010101010101010101010101010101\
+			0000000000000000000000000000000000000000000000000000000000000005\
+			0000000000000000000000000000000000000000000000000000000000000060\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			ab00000000000000000000000000000000000000000000000000000000000000";
+		assert_eq!(encoded.to_hex::<String>(), expected);
+
+		let batch = market::functions::batch::encode_input(vec![order], [Uint::from(1), Uint::from(2)]);
+		assert_eq!(batch.len(), 4 + 32 * 10);
+	}
+
+	#[test]
+	fn encoding_artifact_input_works() {
+		use test_artifact;
+
+		let encoded = test_artifact::functions::foo::encode_input(true);
+		assert_eq!(encoded.to_hex::<String>(), "455575780000000000000000000000000000000000000000000000000000000000000001");
+	}
+
+	#[test]
+	fn encoding_truffle_and_named_artifact_input_works() {
+		use {combined_token, truffle_artifact};
+
+		let encoded = truffle_artifact::functions::get_balance::encode_input(Wrapper([0x11u8; 20]));
+		assert_eq!(encoded.to_hex::<String>(), "f8b2cb4f0000000000000000000000001111111111111111111111111111111111111111");
+
+		let encoded = combined_token::functions::transfer::encode_input(Wrapper([0x11u8; 20]), 42);
+		assert_eq!(encoded.to_hex::<String>(), "a9059cbb0000000000000000000000001111111111111111111111111111111111111111000000000000000000000000000000000000000000000000000000000000002a");
+	}
 }